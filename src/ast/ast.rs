@@ -1,23 +1,508 @@
 use serde::Serialize;
 use std::vec::Vec;
 
-#[derive(Debug, Clone)]
+// a statement is parsed once per query string, so boxing the variants only
+// to even out their sizes isn't worth it
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum Statement {
-    Select(SelectStatement),
+    Query(Query),
+    Insert(InsertStatement),
+    Update(UpdateStatement),
+    Delete(DeleteStatement),
+    Merge(MergeStatement),
+    CreateTable(CreateTableStatement),
+    CreateIndex(CreateIndexStatement),
+    CreateView(CreateViewStatement),
+    AlterTable(AlterTableStatement),
+    Drop(DropStatement),
+    Truncate(TruncateStatement),
+    // BEGIN [TRANSACTION] or START TRANSACTION
+    StartTransaction(Vec<TransactionMode>),
+    SetTransaction(Vec<TransactionMode>),
+    Commit,
+    // ROLLBACK [TO [SAVEPOINT] name]
+    Rollback(Option<String>),
+    Savepoint(String),
+    ReleaseSavepoint(String),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum TransactionMode {
+    IsolationLevel(IsolationLevel),
+    AccessMode(TransactionAccessMode),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum IsolationLevel {
+    ReadUncommitted,
+    ReadCommitted,
+    RepeatableRead,
+    Serializable,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum TransactionAccessMode {
+    ReadOnly,
+    ReadWrite,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct InsertStatement {
+    pub with: Option<With>,
+    pub table: ObjectName,
+    // INSERT INTO bike AS b
+    pub alias: Option<String>,
+    // an empty list means all columns in table order
+    pub columns: Vec<String>,
+    pub source: InsertSource,
+    pub on_conflict: Option<OnConflict>,
+    pub returning: Vec<SelectExpression>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum OnConflict {
+    // Postgres ON CONFLICT [target] DO NOTHING | DO UPDATE SET ...
+    Conflict {
+        target: Option<ConflictTarget>,
+        action: ConflictAction,
+    },
+    // MySQL ON DUPLICATE KEY UPDATE column = value, ...
+    DuplicateKeyUpdate(Vec<Assignment>),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum ConflictTarget {
+    Columns(Vec<String>),
+    // ON CONSTRAINT name
+    Constraint(ObjectName),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum ConflictAction {
+    DoNothing,
+    DoUpdate {
+        assignments: Vec<Assignment>,
+        where_expression: Option<Term>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UpdateStatement {
+    pub with: Option<With>,
+    pub table: ObjectName,
+    pub alias: Option<String>,
+    pub assignments: Vec<Assignment>,
+    // Postgres UPDATE ... FROM, the tables are joined to the target by the where clause
+    pub from_expressions: Vec<TableExpression>,
+    pub where_expression: Option<Term>,
+    pub returning: Vec<SelectExpression>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DeleteStatement {
+    pub with: Option<With>,
+    // MySQL DELETE t1, t2 FROM t1 JOIN t2 ..., empty when deleting from the
+    // table in the from list
+    pub targets: Vec<ObjectName>,
+    pub from_expressions: Vec<TableExpression>,
+    // Postgres DELETE FROM t USING other_tables
+    pub using: Vec<TableExpression>,
+    pub where_expression: Option<Term>,
+    pub returning: Vec<SelectExpression>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MergeStatement {
+    pub with: Option<With>,
+    pub target: ObjectName,
+    pub alias: Option<String>,
+    // USING table or (subquery) AS alias
+    pub source: TableExpression,
+    pub on: Term,
+    // evaluated in order, the first clause whose kind and condition match wins
+    pub clauses: Vec<MergeClause>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MergeClause {
+    pub kind: MergeClauseKind,
+    // WHEN MATCHED AND condition THEN ...
+    pub condition: Option<Term>,
+    pub action: MergeAction,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum MergeClauseKind {
+    Matched,
+    // WHEN NOT MATCHED [BY TARGET], a source row without a target row
+    NotMatched,
+    // WHEN NOT MATCHED BY SOURCE, a target row without a source row
+    NotMatchedBySource,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum MergeAction {
+    Update(Vec<Assignment>),
+    Delete,
+    Insert {
+        columns: Vec<String>,
+        // None for INSERT DEFAULT VALUES
+        values: Option<Vec<Term>>,
+    },
+    // Postgres DO NOTHING
+    DoNothing,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CreateTableStatement {
+    pub temporary: bool,
+    pub if_not_exists: bool,
+    pub name: ObjectName,
+    pub columns: Vec<ColumnDefinition>,
+    pub constraints: Vec<TableConstraint>,
+    // CREATE TABLE ... AS query, the columns come from the query
+    pub query: Option<Box<Query>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CreateIndexStatement {
+    pub unique: bool,
+    pub concurrently: bool,
+    pub if_not_exists: bool,
+    pub name: String,
+    pub table: ObjectName,
+    // USING btree, gin, ...
+    pub method: Option<String>,
+    // columns or expressions with the same ASC/DESC and NULLS options as ORDER BY
+    pub columns: Vec<OrderByExpr>,
+    // INCLUDE (column, ...) stores extra columns without indexing them
+    pub include: Vec<String>,
+    // a partial index only covers rows matching the where clause
+    pub where_expression: Option<Term>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CreateViewStatement {
+    pub or_replace: bool,
+    pub materialized: bool,
+    pub name: ObjectName,
+    pub columns: Vec<String>,
+    pub query: Box<Query>,
+    // WITH DATA or WITH NO DATA, only for materialized views
+    pub with_data: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ColumnDefinition {
+    pub name: String,
+    pub data_type: DataType,
+    pub constraints: Vec<ColumnConstraint>,
+}
+
+// [CONSTRAINT name] option
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ColumnConstraint {
+    pub name: Option<String>,
+    pub option: ColumnOption,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum ColumnOption {
+    NotNull,
+    Null,
+    Default(Term),
+    PrimaryKey,
+    Unique,
+    References(ForeignKeyReference),
+    Check(Term),
+    Collate(ObjectName),
+}
+
+// REFERENCES table [(column, ...)] [ON DELETE action] [ON UPDATE action]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ForeignKeyReference {
+    pub table: ObjectName,
+    // empty when referencing the primary key
+    pub columns: Vec<String>,
+    pub on_delete: Option<ReferentialAction>,
+    pub on_update: Option<ReferentialAction>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum ReferentialAction {
+    NoAction,
+    Restrict,
+    Cascade,
+    SetNull,
+    SetDefault,
+}
+
+// [CONSTRAINT name] constraint, declared next to the column definitions
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TableConstraint {
+    pub name: Option<String>,
+    pub kind: TableConstraintKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum TableConstraintKind {
+    PrimaryKey(Vec<String>),
+    Unique(Vec<String>),
+    ForeignKey {
+        columns: Vec<String>,
+        references: ForeignKeyReference,
+    },
+    Check(Term),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AlterTableStatement {
+    pub if_exists: bool,
+    pub name: ObjectName,
+    // comma separated operations are applied in order
+    pub operations: Vec<AlterTableOperation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum AlterTableOperation {
+    AddColumn {
+        if_not_exists: bool,
+        column: ColumnDefinition,
+    },
+    DropColumn {
+        if_exists: bool,
+        name: String,
+        drop_behavior: Option<DropBehavior>,
+    },
+    RenameColumn {
+        old_name: String,
+        new_name: String,
+    },
+    AlterColumn {
+        name: String,
+        operation: AlterColumnOperation,
+    },
+    AddConstraint(TableConstraint),
+    DropConstraint {
+        if_exists: bool,
+        name: String,
+        drop_behavior: Option<DropBehavior>,
+    },
+    // RENAME TO new_name
+    RenameTable(ObjectName),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum AlterColumnOperation {
+    // [SET DATA] TYPE data_type [USING expression]
+    SetDataType {
+        data_type: DataType,
+        using: Option<Term>,
+    },
+    SetDefault(Term),
+    DropDefault,
+    SetNotNull,
+    DropNotNull,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DropStatement {
+    pub object_type: ObjectType,
+    pub if_exists: bool,
+    pub names: Vec<DropName>,
+    pub drop_behavior: Option<DropBehavior>,
+}
+
+// a name in a DROP list, functions can be followed by their parameter types
+// to pick one overload, e.g. DROP FUNCTION add(int, int)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DropName {
+    pub name: ObjectName,
+    pub parameter_types: Option<Vec<DataType>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum ObjectType {
+    Table,
+    View,
+    MaterializedView,
+    Index,
+    Schema,
+    Sequence,
+    Type,
+    Function,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TruncateStatement {
+    pub names: Vec<ObjectName>,
+    // RESTART IDENTITY resets owned sequences, CONTINUE IDENTITY is the default
+    pub restart_identity: bool,
+    pub drop_behavior: Option<DropBehavior>,
+}
+
+// what happens to dependent objects when dropping something
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum DropBehavior {
+    Cascade,
+    Restrict,
+}
+
+// column = value or (column, ...) = (value, ...) in a SET clause
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Assignment {
+    pub target: AssignmentTarget,
+    // a Term::Row or Term::Subquery when the target is a tuple
+    pub value: Term,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum AssignmentTarget {
+    Column(ObjectName),
+    Tuple(Vec<ObjectName>),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum InsertSource {
+    // VALUES (...) is parsed as a query with a SetExpression::Values body
+    Query(Box<Query>),
+    DefaultValues,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Query {
+    pub with: Option<With>,
+    pub body: SetExpression,
+    // ORDER BY and the row limit apply to the whole compound query
+    pub order_by: Vec<OrderByExpr>,
+    pub limit: Option<RowLimit>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum SetExpression {
+    Select(Box<SelectStatement>),
+    // a parenthesised query, e.g. (SELECT ... LIMIT 1) UNION (SELECT ...)
+    Query(Box<Query>),
+    // VALUES (1, 'a'), (2, 'b')
+    Values(Vec<Vec<Term>>),
+    // INTERSECT binds tighter than UNION and EXCEPT, all are left associative
+    SetOperation {
+        operator: SetOperator,
+        // ALL or DISTINCT, DISTINCT is implied when neither is given
+        quantifier: Option<SetQuantifier>,
+        left: Box<SetExpression>,
+        right: Box<SetExpression>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum SetOperator {
+    Union,
+    Intersect,
+    Except,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct With {
+    pub recursive: bool,
+    pub common_table_expressions: Vec<CommonTableExpression>,
+}
+
+// name [(column, ...)] AS [[NOT] MATERIALIZED] (query) [SEARCH ...] [CYCLE ...]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CommonTableExpression {
+    pub name: String,
+    pub columns: Vec<String>,
+    // None when neither MATERIALIZED nor NOT MATERIALIZED is given
+    pub materialized: Option<bool>,
+    pub query: Box<Query>,
+    pub search: Option<CteSearch>,
+    pub cycle: Option<CteCycle>,
+}
+
+// SEARCH { DEPTH | BREADTH } FIRST BY column, ... SET sequence_column
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CteSearch {
+    pub order: SearchOrder,
+    pub columns: Vec<String>,
+    pub sequence_column: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum SearchOrder {
+    DepthFirst,
+    BreadthFirst,
+}
+
+// CYCLE column, ... SET mark_column [TO mark_value DEFAULT default_value] USING path_column
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CteCycle {
+    pub columns: Vec<String>,
+    pub mark_column: String,
+    pub mark_value: Option<Term>,
+    pub default_value: Option<Term>,
+    pub path_column: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct SelectStatement {
+    pub set_quantifier: Option<SetQuantifier>,
+    // T-SQL TOP (n) limits this select rather than the whole compound query
+    pub top: Option<RowLimit>,
     pub select_expressions: Vec<SelectExpression>,
-    pub from_expression: TableExpression,
+    // comma separated tables in the FROM clause
+    pub from_expressions: Vec<TableExpression>,
     pub where_expression: Option<Term>,
+    pub group_by: Option<GroupBy>,
+    pub having: Option<Term>,
+    // WINDOW w AS (...)
+    pub named_windows: Vec<NamedWindow>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum SetQuantifier {
+    All,
+    Distinct,
+    // DISTINCT ON (a, b) keeps the first row of each distinct (a, b)
+    DistinctOn(Vec<Term>),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum GroupBy {
+    // GROUP BY ALL groups by every select expression that is not an aggregate
+    All,
+    Elements(Vec<GroupingElement>),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum GroupingElement {
+    Expression(Term),
+    // a parenthesised set of expressions like (a, b), or () for the empty set
+    Set(Vec<Term>),
+    Rollup(Vec<GroupingElement>),
+    Cube(Vec<GroupingElement>),
+    GroupingSets(Vec<GroupingElement>),
 }
 
-#[derive(Debug, Clone)]
+// LIMIT, OFFSET, FETCH FIRST and TOP all describe the same row limiting
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RowLimit {
+    // the number of rows to return, None for LIMIT ALL or only an OFFSET
+    pub count: Option<Term>,
+    pub offset: Option<Term>,
+    // TOP (n) PERCENT
+    pub percent: bool,
+    // FETCH FIRST n ROWS WITH TIES
+    pub with_ties: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum SelectExpression {
     Star,
+    // qualifier.*
     Family {
-        name: String,
+        name: ObjectName,
     },
     Expression {
         expression: Term,
@@ -25,24 +510,128 @@ pub enum SelectExpression {
     },
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum Term {
     Value(Value),
-    // BindParameter -> ?,:1 so data can be inject into the query, kind of like a template
+    // so data can be injected into the query, kind of like a template
+    BindParameter(BindParameter),
     Function(Function),
     Operand(Box<Operand>),
     Column(Column),
+    Cast {
+        kind: CastKind,
+        expression: Box<Term>,
+        data_type: DataType,
+    },
+    // ARRAY[1, 2, 3]
+    Array(Vec<Term>),
+    // a subquery used as a value, e.g. (SELECT max(price) FROM bike)
+    Subquery(Box<Query>),
+    // [NOT] EXISTS (SELECT ...)
+    Exists {
+        negated: bool,
+        subquery: Box<Query>,
+    },
+    // the right hand side of a quantified comparison like x > ALL (SELECT ...)
+    // or x = ANY (ARRAY[...]), the expression is either a subquery or an array
+    Quantified {
+        quantifier: Quantifier,
+        expression: Box<Term>,
+    },
+    // the DEFAULT keyword in place of a value, e.g. VALUES (1, DEFAULT)
+    Default,
+    // a parenthesised list of values, e.g. SET (a, b) = (1, 2)
+    Row(Vec<Term>),
 }
 
-#[derive(Debug, Clone, Serialize)]
-pub enum Column {
-    Direct {
-        schema: Option<String>,
-        column_name: String,
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum Quantifier {
+    All,
+    Any,
+    Some,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum CastKind {
+    // CAST(x AS type)
+    Cast,
+    // TRY_CAST(x AS type) and SAFE_CAST(x AS type) produce null instead of an error
+    TryCast,
+    SafeCast,
+    // x::type
+    DoubleColon,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum DataType {
+    // integers
+    TinyInt,
+    SmallInt,
+    Integer,
+    BigInt,
+    // exact and approximate numerics
+    Decimal {
+        precision: Option<u64>,
+        scale: Option<u64>,
+    },
+    Real,
+    Double,
+    Float(Option<u64>),
+    // character strings
+    Char(Option<u64>),
+    Varchar(Option<u64>),
+    Text,
+    Boolean,
+    // date and time
+    Date,
+    Time {
+        precision: Option<u64>,
+        with_time_zone: bool,
+    },
+    Timestamp {
+        precision: Option<u64>,
+        with_time_zone: bool,
+    },
+    Interval,
+    // dialect specific types
+    Json,
+    Jsonb,
+    Uuid,
+    Bytea,
+    Blob,
+    // element type with an optional fixed size, e.g. int[] or int[3]
+    Array {
+        element_type: Box<DataType>,
+        size: Option<u64>,
+    },
+    // any type name not listed above, e.g. a domain or enum type
+    Custom {
+        name: ObjectName,
+        modifiers: Vec<String>,
     },
 }
 
-#[derive(Debug, Clone, Serialize)]
+// a dot separated name like db.schema.table.column, resolving what each
+// part refers to is left to later stages
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ObjectName(pub Vec<String>);
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum Column {
+    Direct { name: ObjectName },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum BindParameter {
+    // ?
+    Anonymous,
+    // $1 or :1
+    Positional(u64),
+    // :name
+    Named(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum Value {
     String(String),
     Numeric(Numeric),
@@ -50,26 +639,138 @@ pub enum Value {
     Null,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum Numeric {
     Float(f64),
     Int(i64),
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum Function {
-    UserDefined { name: String, terms: Vec<Term> },
-    Sum(Box<Term>),
+    UserDefined {
+        name: ObjectName,
+        terms: Vec<Term>,
+    },
+    Aggregate {
+        function: AggregateFunction,
+        // COUNT(DISTINCT x)
+        distinct: bool,
+        // STRING_AGG(x, ',' ORDER BY y)
+        order_by: Vec<OrderByExpr>,
+        // PERCENTILE_CONT(0.5) WITHIN GROUP (ORDER BY y)
+        within_group: Vec<OrderByExpr>,
+        // SUM(x) FILTER (WHERE y > 0)
+        filter: Option<Box<Term>>,
+    },
+    // GROUPING(a, b) tells which expressions are aggregated in a grouping set
+    Grouping(Vec<Term>),
+    // any function evaluated over a window, e.g. ROW_NUMBER() OVER (...)
+    Window {
+        function: Box<Function>,
+        window: Window,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum AggregateFunction {
     Count(CountFunction),
+    Sum(Box<Term>),
+    Avg(Box<Term>),
+    Min(Box<Term>),
+    Max(Box<Term>),
+    StringAgg {
+        expression: Box<Term>,
+        delimiter: Box<Term>,
+    },
+    ArrayAgg(Box<Term>),
+    PercentileCont(Box<Term>),
+    PercentileDisc(Box<Term>),
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum CountFunction {
     Star,
     Term(Box<Term>),
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum Window {
+    // OVER w
+    Named(String),
+    // OVER (...)
+    Specification(WindowSpecification),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NamedWindow {
+    pub name: String,
+    pub specification: WindowSpecification,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WindowSpecification {
+    // OVER (w ORDER BY x) refines the existing window w
+    pub existing_window: Option<String>,
+    pub partition_by: Vec<Term>,
+    pub order_by: Vec<OrderByExpr>,
+    pub frame: Option<WindowFrame>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WindowFrame {
+    pub units: FrameUnits,
+    pub start: FrameBound,
+    // only set when the frame is written as BETWEEN start AND end
+    pub end: Option<FrameBound>,
+    pub exclusion: Option<FrameExclusion>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum FrameUnits {
+    Rows,
+    Range,
+    Groups,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum FrameBound {
+    UnboundedPreceding,
+    Preceding(Box<Term>),
+    CurrentRow,
+    Following(Box<Term>),
+    UnboundedFollowing,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum FrameExclusion {
+    CurrentRow,
+    Group,
+    Ties,
+    NoOthers,
+}
+
+// the expression can also be a select list alias, which parses as a column,
+// or an ordinal position, which parses as an integer
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OrderByExpr {
+    pub expression: Term,
+    pub direction: Option<OrderDirection>,
+    pub nulls: Option<NullsOrder>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum OrderDirection {
+    Asc,
+    Desc,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum NullsOrder {
+    First,
+    Last,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum Operand {
     // term
     Term(Term),
@@ -96,14 +797,69 @@ pub enum Operand {
     GreaterThanOrEqual(Box<Operand>, Box<Operand>),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum TableExpression {
     Table {
-        schema: Option<String>,
-        table: String,
+        name: ObjectName,
+        alias: Option<TableAlias>,
     },
     Select {
-        select_statement: Box<SelectStatement>,
-        alias: Option<String>,
+        // LATERAL (SELECT ...) can reference columns of preceding FROM items
+        lateral: bool,
+        query: Box<Query>,
+        alias: Option<TableAlias>,
+    },
+    // a set returning function like generate_series(1, 10) AS g(n)
+    Function {
+        lateral: bool,
+        function: Function,
+        with_ordinality: bool,
+        alias: Option<TableAlias>,
+    },
+    // UNNEST(array, ...) [WITH ORDINALITY]
+    Unnest {
+        lateral: bool,
+        expressions: Vec<Term>,
+        with_ordinality: bool,
+        alias: Option<TableAlias>,
     },
+    // joins keep the exact structure they were written with, chained joins
+    // are left associative unless parentheses say otherwise
+    Join {
+        left: Box<TableExpression>,
+        right: Box<TableExpression>,
+        join_type: JoinType,
+        constraint: JoinConstraint,
+    },
+    // a parenthesised join like (a JOIN b ON ...)
+    Nested(Box<TableExpression>),
+}
+
+// AS name (column, ...) where both AS and the column list are optional
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TableAlias {
+    pub name: String,
+    pub columns: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum JoinType {
+    Inner,
+    Left,
+    Right,
+    Full,
+    Cross,
+    // T-SQL CROSS APPLY and OUTER APPLY, the right side is evaluated per row
+    // of the left side and there is no join constraint
+    CrossApply,
+    OuterApply,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum JoinConstraint {
+    On(Term),
+    Using(Vec<String>),
+    Natural,
+    // CROSS JOIN
+    None,
 }
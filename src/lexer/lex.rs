@@ -20,11 +20,11 @@ struct StaticToken {
 }
 
 impl QuoteType {
-    fn to_string(&self) -> String {
-        match &self {
-            QuoteType::Single => "'".to_string(),
-            QuoteType::Double => "\"".to_string(),
-            QuoteType::Backtick => "`".to_string(),
+    fn as_str(self) -> &'static str {
+        match self {
+            QuoteType::Single => "'",
+            QuoteType::Double => "\"",
+            QuoteType::Backtick => "`",
         }
     }
 }
@@ -62,6 +62,50 @@ pub enum Token {
     In,
     True,
     False,
+    Cast,
+    With,
+    Exists,
+    All,
+    Any,
+    Some,
+    Array,
+    Distinct,
+    Group,
+    Over,
+    Window,
+    Between,
+    Offset,
+    Fetch,
+    Having,
+    On,
+    Join,
+    Inner,
+    Left,
+    Right,
+    Full,
+    Outer,
+    Cross,
+    Natural,
+    Using,
+    Recursive,
+    Set,
+    Default,
+    Union,
+    Intersect,
+    Except,
+    Values,
+    Insert,
+    Into,
+    Update,
+    Delete,
+    Returning,
+    Merge,
+    When,
+    Then,
+    Create,
+    Alter,
+    Drop,
+    Truncate,
     // symbols
     Star,
     Comma,
@@ -79,6 +123,12 @@ pub enum Token {
     Plus,
     Minus,
     ForwardSlash,
+    Colon,
+    DoubleColon,
+    LeftBracket,
+    RightBracket,
+    QuestionMark,
+    Dollar,
     // data literals
     Number(String),
     StringToken(String),
@@ -94,28 +144,28 @@ impl Token {
             (Token::Number(_), Token::Number(_)) => true,
             (Token::StringToken(_), Token::StringToken(_)) => true,
             (Token::Identifier(_), Token::Identifier(_)) => true,
-            _ => &t1 == &t2,
+            _ => t1 == t2,
         }
     }
     pub fn is_expression_operator(&self) -> bool {
-        match self {
-            Token::And => true,
-            Token::Or => true,
-            Token::Not => true,
-            Token::Is => true,
-            Token::In => true,
-            Token::LessThan => true,
-            Token::LessThanEqual => true,
-            Token::GreaterThan => true,
-            Token::GreaterThanEqual => true,
-            Token::Equal => true,
-            Token::NotEqual => true,
-            Token::Plus => true,
-            Token::Minus => true,
-            Token::Star => true,
-            Token::ForwardSlash => true,
-            _ => false,
-        }
+        matches!(
+            self,
+            Token::And
+                | Token::Or
+                | Token::Not
+                | Token::Is
+                | Token::In
+                | Token::LessThan
+                | Token::LessThanEqual
+                | Token::GreaterThan
+                | Token::GreaterThanEqual
+                | Token::Equal
+                | Token::NotEqual
+                | Token::Plus
+                | Token::Minus
+                | Token::Star
+                | Token::ForwardSlash
+        )
     }
 }
 
@@ -134,12 +184,12 @@ struct QuotedTokenizer {
 impl Tokenizer for QuotedTokenizer {
     fn add_next_character(&mut self, c: &str) -> (bool, bool) {
         if let Some(last) = self.text.last() {
-            if last == "\\" && self.quote_type.to_string() == c {
+            if last == "\\" && self.quote_type.as_str() == c {
                 self.text.push(c.to_string());
                 return (false, true);
             }
         }
-        if c == self.quote_type.to_string() {
+        if c == self.quote_type.as_str() {
             (true, true)
         } else {
             self.text.push(c.to_string());
@@ -165,10 +215,7 @@ impl QuotedTokenizer {
     }
 
     fn is_valid_starting_character(c: &str) -> bool {
-        match QuoteType::try_from(c) {
-            Ok(_) => true,
-            Err(_) => false,
-        }
+        QuoteType::try_from(c).is_ok()
     }
 }
 
@@ -295,6 +342,182 @@ impl KeywordTokenizer {
                 token: Token::False,
                 text: "false".to_string(),
             },
+            StaticToken {
+                token: Token::Cast,
+                text: "cast".to_string(),
+            },
+            StaticToken {
+                token: Token::With,
+                text: "with".to_string(),
+            },
+            StaticToken {
+                token: Token::Exists,
+                text: "exists".to_string(),
+            },
+            StaticToken {
+                token: Token::All,
+                text: "all".to_string(),
+            },
+            StaticToken {
+                token: Token::Any,
+                text: "any".to_string(),
+            },
+            StaticToken {
+                token: Token::Some,
+                text: "some".to_string(),
+            },
+            StaticToken {
+                token: Token::Array,
+                text: "array".to_string(),
+            },
+            StaticToken {
+                token: Token::Distinct,
+                text: "distinct".to_string(),
+            },
+            StaticToken {
+                token: Token::Group,
+                text: "group".to_string(),
+            },
+            StaticToken {
+                token: Token::Over,
+                text: "over".to_string(),
+            },
+            StaticToken {
+                token: Token::Window,
+                text: "window".to_string(),
+            },
+            StaticToken {
+                token: Token::Between,
+                text: "between".to_string(),
+            },
+            StaticToken {
+                token: Token::Offset,
+                text: "offset".to_string(),
+            },
+            StaticToken {
+                token: Token::Fetch,
+                text: "fetch".to_string(),
+            },
+            StaticToken {
+                token: Token::Having,
+                text: "having".to_string(),
+            },
+            StaticToken {
+                token: Token::On,
+                text: "on".to_string(),
+            },
+            StaticToken {
+                token: Token::Join,
+                text: "join".to_string(),
+            },
+            StaticToken {
+                token: Token::Inner,
+                text: "inner".to_string(),
+            },
+            StaticToken {
+                token: Token::Left,
+                text: "left".to_string(),
+            },
+            StaticToken {
+                token: Token::Right,
+                text: "right".to_string(),
+            },
+            StaticToken {
+                token: Token::Full,
+                text: "full".to_string(),
+            },
+            StaticToken {
+                token: Token::Outer,
+                text: "outer".to_string(),
+            },
+            StaticToken {
+                token: Token::Cross,
+                text: "cross".to_string(),
+            },
+            StaticToken {
+                token: Token::Natural,
+                text: "natural".to_string(),
+            },
+            StaticToken {
+                token: Token::Using,
+                text: "using".to_string(),
+            },
+            StaticToken {
+                token: Token::Recursive,
+                text: "recursive".to_string(),
+            },
+            StaticToken {
+                token: Token::Set,
+                text: "set".to_string(),
+            },
+            StaticToken {
+                token: Token::Default,
+                text: "default".to_string(),
+            },
+            StaticToken {
+                token: Token::Union,
+                text: "union".to_string(),
+            },
+            StaticToken {
+                token: Token::Intersect,
+                text: "intersect".to_string(),
+            },
+            StaticToken {
+                token: Token::Except,
+                text: "except".to_string(),
+            },
+            StaticToken {
+                token: Token::Values,
+                text: "values".to_string(),
+            },
+            StaticToken {
+                token: Token::Insert,
+                text: "insert".to_string(),
+            },
+            StaticToken {
+                token: Token::Into,
+                text: "into".to_string(),
+            },
+            StaticToken {
+                token: Token::Update,
+                text: "update".to_string(),
+            },
+            StaticToken {
+                token: Token::Delete,
+                text: "delete".to_string(),
+            },
+            StaticToken {
+                token: Token::Returning,
+                text: "returning".to_string(),
+            },
+            StaticToken {
+                token: Token::Merge,
+                text: "merge".to_string(),
+            },
+            StaticToken {
+                token: Token::When,
+                text: "when".to_string(),
+            },
+            StaticToken {
+                token: Token::Then,
+                text: "then".to_string(),
+            },
+            StaticToken {
+                token: Token::Create,
+                text: "create".to_string(),
+            },
+            StaticToken {
+                token: Token::Alter,
+                text: "alter".to_string(),
+            },
+            StaticToken {
+                token: Token::Drop,
+                text: "drop".to_string(),
+            },
+            StaticToken {
+                token: Token::Truncate,
+                text: "truncate".to_string(),
+            },
         ];
         keywords
     }
@@ -318,17 +541,13 @@ struct SymbolTokenizer {
 
 impl Tokenizer for SymbolTokenizer {
     fn add_next_character(&mut self, c: &str) -> (bool, bool) {
-        if self.text.len() == 1 {
-            if let Some(t) = self.text.last() {
-                if (t.to_string() == '>'.to_string() || t.to_string() == '<'.to_string())
-                    && c.to_string() == "=".to_string()
-                {
-                    self.text.push(c.to_string());
-                    return (true, true);
-                }
-            }
-        } else {
-            return (true, false);
+        // symbols are at most two characters long, so only a single
+        // character can ever be appended to the first one
+        if self.text.len() == 1
+            && SymbolTokenizer::token_from_string(format!("{}{}", self.text.concat(), c)).is_some()
+        {
+            self.text.push(c.to_string());
+            return (true, true);
         }
         (true, false)
     }
@@ -429,6 +648,30 @@ impl SymbolTokenizer {
                 token: Token::ForwardSlash,
                 text: "/".to_string(),
             },
+            StaticToken {
+                token: Token::Colon,
+                text: ":".to_string(),
+            },
+            StaticToken {
+                token: Token::DoubleColon,
+                text: "::".to_string(),
+            },
+            StaticToken {
+                token: Token::LeftBracket,
+                text: "[".to_string(),
+            },
+            StaticToken {
+                token: Token::RightBracket,
+                text: "]".to_string(),
+            },
+            StaticToken {
+                token: Token::QuestionMark,
+                text: "?".to_string(),
+            },
+            StaticToken {
+                token: Token::Dollar,
+                text: "$".to_string(),
+            },
         ];
         keywords
     }
@@ -2,7 +2,7 @@ use crate::lexer::lex::Token;
 
 use super::lex;
 
-fn vecs_equal<T: PartialEq>(a: &Vec<T>, b: &Vec<T>) -> bool {
+fn vecs_equal<T: PartialEq>(a: &[T], b: &[T]) -> bool {
     let matching = a.iter().zip(b.iter()).filter(|&(a, b)| a == b).count();
     matching == a.len() && matching == b.len()
 }
@@ -189,6 +189,34 @@ fn test_lex_with_basic_sql_statements() {
                 lex::Token::Semicolon,
             ],
         },
+        TestCase {
+            case_name: String::from("sample-4"),
+            query: String::from("select cast(a as int), b::text[] from bike;"),
+            expected_tokens: vec![
+                lex::Token::Select,
+                lex::Token::Space,
+                lex::Token::Cast,
+                lex::Token::LeftParenthesis,
+                lex::Token::Identifier("a".to_string()),
+                lex::Token::Space,
+                lex::Token::As,
+                lex::Token::Space,
+                lex::Token::Identifier("int".to_string()),
+                lex::Token::RightParenthesis,
+                lex::Token::Comma,
+                lex::Token::Space,
+                lex::Token::Identifier("b".to_string()),
+                lex::Token::DoubleColon,
+                lex::Token::Identifier("text".to_string()),
+                lex::Token::LeftBracket,
+                lex::Token::RightBracket,
+                lex::Token::Space,
+                lex::Token::From,
+                lex::Token::Space,
+                lex::Token::Identifier("bike".to_string()),
+                lex::Token::Semicolon,
+            ],
+        },
     ];
 
     for test_case in test_cases {
@@ -196,6 +224,6 @@ fn test_lex_with_basic_sql_statements() {
         let tokens = lex::lex(test_case.query);
         println!("expected: {:?}", test_case.expected_tokens);
         println!("actual: {:?}", tokens);
-        assert_eq!(vecs_equal(&tokens, &test_case.expected_tokens), true);
+        assert!(vecs_equal(&tokens, &test_case.expected_tokens));
     }
 }
@@ -1,5 +1,3 @@
-use core::sync;
-
 use sqlparser::ast::ast;
 use sqlparser::lexer::lex;
 use sqlparser::parser::parser;
@@ -69,9 +67,12 @@ fn main() {
             println!("syntax tree:");
             println!("{:?}", syntax_tree);
             match syntax_tree {
-                ast::Statement::Select(select) => {
-                    print_where_expression_tree(select.where_expression)
-                }
+                ast::Statement::Query(query) => match query.body {
+                    ast::SetExpression::Select(select) => {
+                        print_where_expression_tree(select.where_expression)
+                    }
+                    _ => println!("couldn't print tree"),
+                },
                 _ => println!("couldn't print tree"),
             }
         }
@@ -1 +1,4 @@
+#[allow(clippy::module_inception)]
 pub mod parser;
+#[cfg(test)]
+mod test_parser;
@@ -2,8 +2,19 @@ use anyhow::{Context, Result};
 use thiserror::Error;
 
 use crate::ast::ast::{
-    Column, Function, Numeric, Operand, SelectExpression, SelectStatement, Statement,
-    TableExpression, Term, Value,
+    AggregateFunction, AlterColumnOperation, AlterTableOperation, AlterTableStatement, Assignment,
+    AssignmentTarget, BindParameter, CastKind, Column, ColumnConstraint, ColumnDefinition,
+    ColumnOption, CommonTableExpression, ConflictAction, ConflictTarget, CountFunction,
+    CreateIndexStatement, CreateTableStatement, CreateViewStatement, CteCycle, CteSearch, DataType,
+    DeleteStatement, DropBehavior, DropName, DropStatement, ForeignKeyReference, FrameBound,
+    FrameExclusion, FrameUnits, Function, GroupBy, GroupingElement, InsertSource, InsertStatement,
+    IsolationLevel, JoinConstraint, JoinType, MergeAction, MergeClause, MergeClauseKind,
+    MergeStatement, NamedWindow, NullsOrder, Numeric, ObjectName, ObjectType, OnConflict, Operand,
+    OrderByExpr, OrderDirection, Quantifier, Query, ReferentialAction, RowLimit, SearchOrder,
+    SelectExpression, SelectStatement, SetExpression, SetOperator, SetQuantifier, Statement,
+    TableAlias, TableConstraint, TableConstraintKind, TableExpression, Term, TransactionAccessMode,
+    TransactionMode, TruncateStatement, UpdateStatement, Value, Window, WindowFrame,
+    WindowSpecification, With,
 };
 use crate::lexer::lex;
 use crate::lexer::lex::Token;
@@ -26,6 +37,10 @@ pub enum ParseError {
     OperandCompactionIssue(String),
     #[error("not implemented: {0}")]
     NotImplemented(String),
+    #[error("invalid number of arguments for {0}: {1}")]
+    InvalidArgumentCount(String, usize),
+    #[error("invalid merge clause: {0}")]
+    InvalidMergeClause(String),
 }
 
 #[derive(Debug)]
@@ -65,15 +80,67 @@ impl Parser {
         self.token_index < self.tokens.len()
     }
 
+    // returns the nth token from the current position ignoring spaces
+    fn peek_nth_token(&self, n: usize) -> Option<&Token> {
+        self.tokens
+            .iter()
+            .skip(self.token_index)
+            .filter(|&token| *token != Token::Space)
+            .nth(n)
+    }
+
     fn peek_match_token_types(&mut self, expected_tokens: Vec<Token>) -> bool {
-        if self.token_index + expected_tokens.len() > self.tokens.len() {
-            return false;
+        expected_tokens
+            .iter()
+            .enumerate()
+            .all(|(idx, t)| match self.peek_nth_token(idx) {
+                Some(token) => lex::Token::token_types_match(t.clone(), token.clone()),
+                None => false,
+            })
+    }
+
+    // non-reserved keywords, like type names, are lexed as identifiers so
+    // they can still be used to name tables and columns
+    fn peek_word(&self, n: usize, word: &str) -> bool {
+        match self.peek_nth_token(n) {
+            Some(Token::Identifier(name)) => name.eq_ignore_ascii_case(word),
+            _ => false,
         }
+    }
 
-        expected_tokens.iter().enumerate().all(|(idx, t)| {
-            let token = &self.tokens[self.token_index + idx];
-            lex::Token::token_types_match(t.clone(), token.clone())
-        })
+    fn match_word(&mut self, word: &str) -> Result<(), ParseError> {
+        self.log(format!("match_word({})", word));
+        if self.peek_word(0, word) {
+            self.read_next_token();
+            Ok(())
+        } else {
+            Err(ParseError::InvalidNextToken(
+                Token::Identifier(word.to_string()),
+                self.next_token()?,
+            ))
+        }
+    }
+
+    fn match_identifier(&mut self) -> Result<String, ParseError> {
+        match self.next_token()? {
+            Token::Identifier(name) => {
+                self.read_next_token();
+                Ok(name)
+            }
+            ut => Err(ParseError::InvalidToken(ut)),
+        }
+    }
+
+    fn match_unsigned_integer(&mut self) -> Result<u64, ParseError> {
+        match self.next_token()? {
+            Token::Number(value) => {
+                self.read_next_token();
+                value
+                    .parse::<u64>()
+                    .map_err(|_| ParseError::InvalidNumber(value))
+            }
+            ut => Err(ParseError::InvalidToken(ut)),
+        }
     }
 
     fn next_token(&mut self) -> Result<Token, ParseError> {
@@ -85,7 +152,7 @@ impl Parser {
     }
 
     fn match_token(&mut self, expected_token: Token) -> Result<(), ParseError> {
-        self.log(format!("match_token({:?})", expected_token).to_string());
+        self.log(format!("match_token({:?})", expected_token));
         let next_token = self.next_token()?;
         if expected_token == next_token {
             self.read_next_token();
@@ -97,7 +164,7 @@ impl Parser {
 
     pub fn parse(&mut self) -> Result<Statement> {
         self.log("parse()".to_string());
-        if self.tokens.len() == 0 {
+        if self.tokens.is_empty() {
             return Err(ParseError::EmptyQueryString.into());
         }
         if self.next_token()? == Token::Space {
@@ -108,301 +175,2158 @@ impl Parser {
         }
 
         let next_token = self.next_token()?;
-        if next_token == Token::Select {
-            let select_statement = self.match_select().context("failed to match select")?;
-            self.match_token(Token::Semicolon)?;
-            Ok(Statement::Select(select_statement))
-        } else {
-            Err(ParseError::InvalidToken(next_token.clone()).into())
-        }
+        let statement = match next_token {
+            Token::Select
+            | Token::With
+            | Token::Values
+            | Token::LeftParenthesis
+            | Token::Insert
+            | Token::Update
+            | Token::Delete
+            | Token::Merge => {
+                // WITH can also lead a write statement
+                let with = self.match_with().context("failed to match with")?;
+                match self.next_token()? {
+                    Token::Insert => Statement::Insert(
+                        self.match_insert(with).context("failed to match insert")?,
+                    ),
+                    Token::Update => Statement::Update(
+                        self.match_update(with).context("failed to match update")?,
+                    ),
+                    Token::Delete => Statement::Delete(
+                        self.match_delete(with).context("failed to match delete")?,
+                    ),
+                    Token::Merge => {
+                        Statement::Merge(self.match_merge(with).context("failed to match merge")?)
+                    }
+                    _ => Statement::Query(
+                        self.match_query_body(with)
+                            .context("failed to match query")?,
+                    ),
+                }
+            }
+            Token::Create => self.match_create().context("failed to match create")?,
+            Token::Alter => Statement::AlterTable(
+                self.match_alter_table()
+                    .context("failed to match alter table")?,
+            ),
+            Token::Drop => Statement::Drop(self.match_drop().context("failed to match drop")?),
+            Token::Set => {
+                self.match_token(Token::Set)?;
+                self.match_word("transaction")?;
+                let modes = self.match_transaction_modes()?;
+                if modes.is_empty() {
+                    return Err(ParseError::InvalidToken(self.next_token()?).into());
+                }
+                Statement::SetTransaction(modes)
+            }
+            Token::Identifier(_) => self
+                .match_transaction_statement()
+                .context("failed to match transaction statement")?,
+            Token::Truncate => {
+                Statement::Truncate(self.match_truncate().context("failed to match truncate")?)
+            }
+            ut => return Err(ParseError::InvalidToken(ut).into()),
+        };
+        self.match_token(Token::Semicolon)?;
+        Ok(statement)
     }
 
-    fn match_select(&mut self) -> Result<SelectStatement> {
-        self.log("match_select()".to_string());
-
-        self.match_token(Token::Select)?;
+    // matches "INSERT INTO table [AS alias] [(column, ...)] {query | DEFAULT VALUES}"
+    fn match_insert(&mut self, with: Option<With>) -> Result<InsertStatement> {
+        self.log("match_insert()".to_string());
+
+        self.match_token(Token::Insert)?;
+        self.match_token(Token::Into)?;
+        let table = self
+            .match_object_name()
+            .context("failed to match table name")?;
+        let mut alias: Option<String> = None;
+        if self.next_token()? == Token::As {
+            self.match_token(Token::As)?;
+            alias = Some(self.match_identifier()?);
+        }
 
-        let select_expressions = self
-            .match_select_expressions()
-            .context("failed to match select expressions")?;
-        let from_expression = self
-            .match_table_expression()
-            .context("failed to match table expression")?;
-        let where_expression = self
-            .match_where_expression()
-            .context("failed to match where expression")?;
+        // a parenthesised query is a source, not a column list
+        let mut columns: Vec<String> = Vec::new();
+        if self.next_token()? == Token::LeftParenthesis && !self.peek_subquery() {
+            self.match_token(Token::LeftParenthesis)?;
+            columns = self.match_identifier_list()?;
+            self.match_token(Token::RightParenthesis)?;
+        }
 
-        let select_statement = SelectStatement {
-            select_expressions,
-            from_expression,
-            where_expression,
+        let source = if self.next_token()? == Token::Default {
+            self.match_token(Token::Default)?;
+            self.match_token(Token::Values)?;
+            InsertSource::DefaultValues
+        } else if self.next_token()? == Token::Values {
+            // DEFAULT is allowed in a VALUES list that is the whole source, a
+            // VALUES list followed by set operations or ORDER BY is parsed
+            // again from the start as a regular query
+            let start_index = self.token_index;
+            let rows = self.match_values(true)?;
+            if matches!(
+                self.next_token()?,
+                Token::On | Token::Returning | Token::Semicolon
+            ) {
+                InsertSource::Query(Box::new(Query {
+                    with: None,
+                    body: SetExpression::Values(rows),
+                    order_by: Vec::new(),
+                    limit: None,
+                }))
+            } else {
+                self.token_index = start_index;
+                InsertSource::Query(Box::new(self.match_query()?))
+            }
+        } else {
+            InsertSource::Query(Box::new(self.match_query()?))
         };
 
-        Ok(select_statement)
+        // like a tuple assignment, a VALUES row needs a value for every column
+        if let InsertSource::Query(query) = &source {
+            let mut body = &query.body;
+            while let SetExpression::Query(query) = body {
+                body = &query.body;
+            }
+            if let SetExpression::Values(rows) = body {
+                let mismatched_row = rows.iter().find(|row| row.len() != columns.len());
+                if let (false, Some(row)) = (columns.is_empty(), mismatched_row) {
+                    return Err(ParseError::InvalidArgumentCount(
+                        "insert values".to_string(),
+                        row.len(),
+                    )
+                    .into());
+                }
+            }
+        }
+
+        let on_conflict = self
+            .match_on_conflict()
+            .context("failed to match on conflict")?;
+        let returning = self
+            .match_returning()
+            .context("failed to match returning")?;
+
+        Ok(InsertStatement {
+            with,
+            table,
+            alias,
+            columns,
+            source,
+            on_conflict,
+            returning,
+        })
     }
 
-    fn match_select_expressions(&mut self) -> Result<Vec<SelectExpression>> {
-        self.log("match_select_expressions()".to_string());
+    // matches "ON CONFLICT [(column, ...) | ON CONSTRAINT name] DO NOTHING"
+    // or "... DO UPDATE SET ... [WHERE ...]", and "ON DUPLICATE KEY UPDATE ..."
+    fn match_on_conflict(&mut self) -> Result<Option<OnConflict>> {
+        if self.next_token()? != Token::On {
+            return Ok(None);
+        }
+        self.log("match_on_conflict()".to_string());
+        self.match_token(Token::On)?;
+
+        if self.peek_word(0, "duplicate") {
+            self.match_word("duplicate")?;
+            self.match_word("key")?;
+            self.match_token(Token::Update)?;
+            let assignments = self.match_assignment_list()?;
+            return Ok(Some(OnConflict::DuplicateKeyUpdate(assignments)));
+        }
 
-        let mut select_expressions: Vec<SelectExpression> = Vec::new();
+        self.match_word("conflict")?;
+        let target = match self.next_token()? {
+            Token::LeftParenthesis => {
+                self.match_token(Token::LeftParenthesis)?;
+                let columns = self.match_identifier_list()?;
+                self.match_token(Token::RightParenthesis)?;
+                Some(ConflictTarget::Columns(columns))
+            }
+            Token::On => {
+                self.match_token(Token::On)?;
+                self.match_word("constraint")?;
+                Some(ConflictTarget::Constraint(self.match_object_name()?))
+            }
+            _ => None,
+        };
 
-        while self.next_token()? != Token::From {
-            if self.next_token()? == Token::Star {
-                select_expressions.push(SelectExpression::Star);
-                self.match_token(Token::Star)?;
-            } else if self.peek_match_token_types(vec![
-                Token::Identifier("".to_string()),
-                Token::Period,
-                Token::Star,
-            ]) {
-                let id_name = match self.next_token()? {
-                    Token::Identifier(name) => name,
-                    unexpected_token => {
-                        return Err(ParseError::InvalidToken(unexpected_token).into())
-                    }
-                };
+        self.match_word("do")?;
+        let action = if self.peek_word(0, "nothing") {
+            self.match_word("nothing")?;
+            ConflictAction::DoNothing
+        } else {
+            self.match_token(Token::Update)?;
+            self.match_token(Token::Set)?;
+            let assignments = self.match_assignment_list()?;
+            let where_expression = self.match_where_expression()?;
+            ConflictAction::DoUpdate {
+                assignments,
+                where_expression,
+            }
+        };
 
-                select_expressions.push(SelectExpression::Family {
-                    name: id_name.clone(),
-                });
-                self.match_token(Token::Identifier(id_name.clone()))?;
-                self.match_token(Token::Period)?;
-                self.match_token(Token::Star)?;
-            } else {
-                let expression = self.match_expression()?;
-                if self.next_token()? == Token::As {
-                    self.match_token(Token::As)?;
-
-                    let next_token = self.next_token()?;
-                    let id_name = match &next_token {
-                        Token::Identifier(name) => name,
-                        unexpected_token => {
-                            return Err(ParseError::InvalidToken(unexpected_token.clone()).into())
-                        }
-                    };
+        Ok(Some(OnConflict::Conflict { target, action }))
+    }
 
-                    self.match_token(next_token.clone())?;
+    // matches "RETURNING select_expression, ..." after a write statement
+    fn match_returning(&mut self) -> Result<Vec<SelectExpression>> {
+        if self.next_token()? != Token::Returning {
+            return Ok(Vec::new());
+        }
+        self.match_token(Token::Returning)?;
 
-                    select_expressions.push(SelectExpression::Expression {
-                        expression,
-                        alias: Some(id_name.clone()),
-                    });
-                } else {
-                    select_expressions.push(SelectExpression::Expression {
-                        expression,
-                        alias: None,
-                    })
+        let returning = self.match_select_expressions()?;
+        if returning.is_empty() {
+            return Err(ParseError::InvalidToken(self.next_token()?).into());
+        }
+        Ok(returning)
+    }
+
+    // matches "UPDATE table [[AS] alias] SET assignment, ... [FROM ...] [WHERE ...]"
+    fn match_update(&mut self, with: Option<With>) -> Result<UpdateStatement> {
+        self.log("match_update()".to_string());
+
+        self.match_token(Token::Update)?;
+        let table = self
+            .match_object_name()
+            .context("failed to match table name")?;
+        let alias = self.match_alias()?;
+        self.match_token(Token::Set)?;
+        let assignments = self
+            .match_assignment_list()
+            .context("failed to match assignments")?;
+        let from_expressions = self
+            .match_from_expressions()
+            .context("failed to match from expressions")?;
+        let where_expression = self
+            .match_where_expression()
+            .context("failed to match where expression")?;
+        let returning = self
+            .match_returning()
+            .context("failed to match returning")?;
+
+        Ok(UpdateStatement {
+            with,
+            table,
+            alias,
+            assignments,
+            from_expressions,
+            where_expression,
+            returning,
+        })
+    }
+
+    // matches "DELETE [target, ...] FROM table_expression, ... [USING ...] [WHERE ...]"
+    fn match_delete(&mut self, with: Option<With>) -> Result<DeleteStatement> {
+        self.log("match_delete()".to_string());
+
+        self.match_token(Token::Delete)?;
+        let mut targets: Vec<ObjectName> = Vec::new();
+        if self.next_token()? != Token::From {
+            loop {
+                targets.push(self.match_object_name()?);
+                // MySQL also allows t1.* as a target
+                if self.next_token()? == Token::Period {
+                    self.match_token(Token::Period)?;
+                    self.match_token(Token::Star)?;
+                }
+                if self.next_token()? != Token::Comma {
+                    break;
                 }
+                self.match_token(Token::Comma)?;
             }
+        }
 
-            if self.next_token()? != Token::From {
-                self.match_token(Token::Comma)?;
-                if self.next_token()? == Token::From {
-                    return Err(ParseError::InvalidToken(Token::From).into());
+        if self.next_token()? != Token::From {
+            return Err(ParseError::InvalidNextToken(Token::From, self.next_token()?).into());
+        }
+        let from_expressions = if targets.is_empty() {
+            // without targets rows are deleted from a single table, any other
+            // tables are listed in USING
+            self.match_token(Token::From)?;
+            let name = self
+                .match_object_name()
+                .context("failed to match table name")?;
+            let alias = self.match_table_alias()?;
+            vec![TableExpression::Table { name, alias }]
+        } else {
+            self.match_from_expressions()
+                .context("failed to match from expressions")?
+        };
+
+        let mut using: Vec<TableExpression> = Vec::new();
+        if self.next_token()? == Token::Using {
+            self.match_token(Token::Using)?;
+            loop {
+                using.push(self.match_table_expression()?);
+                if self.next_token()? != Token::Comma {
+                    break;
                 }
+                self.match_token(Token::Comma)?;
             }
         }
 
-        Ok(select_expressions)
+        let where_expression = self
+            .match_where_expression()
+            .context("failed to match where expression")?;
+        let returning = self
+            .match_returning()
+            .context("failed to match returning")?;
+
+        Ok(DeleteStatement {
+            with,
+            targets,
+            from_expressions,
+            using,
+            where_expression,
+            returning,
+        })
     }
 
-    fn match_table_expression(&mut self) -> Result<TableExpression> {
-        self.log("match_table_expression()".to_string());
+    // matches "MERGE INTO target [[AS] alias] USING source ON condition WHEN ..."
+    fn match_merge(&mut self, with: Option<With>) -> Result<MergeStatement> {
+        self.log("match_merge()".to_string());
+
+        self.match_token(Token::Merge)?;
+        self.match_token(Token::Into)?;
+        let target = self
+            .match_object_name()
+            .context("failed to match target table")?;
+        let alias = self.match_alias()?;
+        self.match_token(Token::Using)?;
+        let source = self
+            .match_table_factor()
+            .context("failed to match merge source")?;
+        self.match_token(Token::On)?;
+        let on = self.match_expression()?;
+
+        let mut clauses: Vec<MergeClause> = Vec::new();
+        while self.next_token()? == Token::When {
+            clauses.push(self.match_merge_clause()?);
+        }
+        if clauses.is_empty() {
+            return Err(ParseError::InvalidNextToken(Token::When, self.next_token()?).into());
+        }
 
-        self.match_token(Token::From)?;
+        Ok(MergeStatement {
+            with,
+            target,
+            alias,
+            source,
+            on,
+            clauses,
+        })
+    }
 
-        if self.next_token()? == Token::LeftParenthesis {
-            self.match_token(Token::LeftParenthesis)?;
+    // matches "WHEN [NOT] MATCHED [BY SOURCE | BY TARGET] [AND condition] THEN action"
+    fn match_merge_clause(&mut self) -> Result<MergeClause> {
+        self.log("match_merge_clause()".to_string());
+
+        self.match_token(Token::When)?;
+        let kind = if self.next_token()? == Token::Not {
+            self.match_token(Token::Not)?;
+            self.match_word("matched")?;
+            if self.next_token()? == Token::By {
+                self.match_token(Token::By)?;
+                if self.peek_word(0, "source") {
+                    self.match_word("source")?;
+                    MergeClauseKind::NotMatchedBySource
+                } else {
+                    self.match_word("target")?;
+                    MergeClauseKind::NotMatched
+                }
+            } else {
+                MergeClauseKind::NotMatched
+            }
+        } else {
+            self.match_word("matched")?;
+            MergeClauseKind::Matched
+        };
 
-            let select_statement = self.match_select()?;
-            self.match_token(Token::RightParenthesis)?;
+        let mut condition: Option<Term> = None;
+        if self.next_token()? == Token::And {
+            self.match_token(Token::And)?;
+            condition = Some(self.match_expression()?);
+        }
+        self.match_token(Token::Then)?;
 
-            let mut alias: Option<String> = None;
-            if self.next_token()? == Token::As {
-                self.match_token(Token::As)?;
-                let next_token = self.next_token()?;
-                let id_name = match next_token.clone() {
-                    Token::Identifier(name) => name,
-                    unexpected_token => {
-                        return Err(ParseError::InvalidToken(unexpected_token).into())
+        let action = match self.next_token()? {
+            Token::Update => {
+                self.match_token(Token::Update)?;
+                self.match_token(Token::Set)?;
+                MergeAction::Update(self.match_assignment_list()?)
+            }
+            Token::Delete => {
+                self.match_token(Token::Delete)?;
+                MergeAction::Delete
+            }
+            Token::Insert => {
+                self.match_token(Token::Insert)?;
+                let mut columns: Vec<String> = Vec::new();
+                if self.next_token()? == Token::LeftParenthesis {
+                    self.match_token(Token::LeftParenthesis)?;
+                    columns = self.match_identifier_list()?;
+                    self.match_token(Token::RightParenthesis)?;
+                }
+                let values = if self.next_token()? == Token::Default {
+                    self.match_token(Token::Default)?;
+                    self.match_token(Token::Values)?;
+                    None
+                } else {
+                    let mut rows = self.match_values(true)?;
+                    if rows.len() != 1 {
+                        return Err(ParseError::InvalidArgumentCount(
+                            "merge insert values".to_string(),
+                            rows.len(),
+                        )
+                        .into());
                     }
+                    rows.pop()
                 };
-                alias = Some(id_name);
-                self.match_token(next_token)?;
+                MergeAction::Insert { columns, values }
+            }
+            _ => {
+                self.match_word("do")?;
+                self.match_word("nothing")?;
+                MergeAction::DoNothing
             }
+        };
 
-            Ok(TableExpression::Select {
-                select_statement: Box::new(select_statement),
-                alias,
-            })
-        } else if let Token::Identifier(_) = self.next_token()? {
-            let (schema, table) = self
-                .match_table_name()
-                .context("failed to match table name")?;
-            Ok(TableExpression::Table { schema, table })
-        } else {
-            Err(
-                ParseError::NotImplemented("table expression type not implemented".to_string())
-                    .into(),
-            )
+        // inserts only make sense without a target row, updates and deletes
+        // only with one
+        let valid = match action {
+            MergeAction::Insert { .. } => kind == MergeClauseKind::NotMatched,
+            MergeAction::Update(_) | MergeAction::Delete => kind != MergeClauseKind::NotMatched,
+            MergeAction::DoNothing => true,
+        };
+        if !valid {
+            return Err(ParseError::InvalidMergeClause(format!(
+                "{:?} action in a {:?} merge clause",
+                action, kind
+            ))
+            .into());
         }
+
+        Ok(MergeClause {
+            kind,
+            condition,
+            action,
+        })
     }
 
-    fn match_table_name(&mut self) -> Result<(Option<String>, String)> {
-        self.log("match_table_name()".to_string());
+    // matches any "CREATE ..." statement
+    fn match_create(&mut self) -> Result<Statement> {
+        self.log("match_create()".to_string());
 
-        let has_schema_and_table = self.peek_match_token_types(vec![
-            Token::Identifier("".to_string()),
-            Token::Period,
-            Token::Identifier("".to_string()),
-        ]);
+        self.match_token(Token::Create)?;
+        if self.peek_word(0, "temporary") || self.peek_word(0, "temp") || self.peek_word(0, "table")
+        {
+            return Ok(Statement::CreateTable(self.match_create_table()?));
+        }
+        if self.peek_word(0, "unique") || self.peek_word(0, "index") {
+            return Ok(Statement::CreateIndex(self.match_create_index()?));
+        }
+        if self.next_token()? == Token::Or
+            || self.peek_word(0, "materialized")
+            || self.peek_word(0, "view")
+        {
+            return Ok(Statement::CreateView(self.match_create_view()?));
+        }
+        Err(ParseError::NotImplemented(format!("CREATE {:?}", self.next_token()?)).into())
+    }
 
-        let id_name1 = match self.next_token()? {
-            Token::Identifier(name) => name,
-            ut => return Err(ParseError::InvalidToken(ut).into()),
-        };
+    // matches "[UNIQUE] INDEX [CONCURRENTLY] [IF NOT EXISTS] name ON table
+    // [USING method] (column, ...) [INCLUDE (column, ...)] [WHERE ...]", following CREATE
+    fn match_create_index(&mut self) -> Result<CreateIndexStatement> {
+        self.log("match_create_index()".to_string());
 
-        let next_token = self.next_token()?;
-        self.match_token(next_token)?;
+        let mut unique = false;
+        if self.peek_word(0, "unique") {
+            self.match_word("unique")?;
+            unique = true;
+        }
+        self.match_word("index")?;
+        let mut concurrently = false;
+        if self.peek_word(0, "concurrently") {
+            self.match_word("concurrently")?;
+            concurrently = true;
+        }
+        let if_not_exists = self.match_if_not_exists()?;
+        let name = self.match_identifier()?;
+        self.match_token(Token::On)?;
+        let table = self
+            .match_object_name()
+            .context("failed to match table name")?;
+
+        let mut method: Option<String> = None;
+        if self.next_token()? == Token::Using {
+            self.match_token(Token::Using)?;
+            method = Some(self.match_identifier()?);
+        }
 
-        if has_schema_and_table {
-            self.match_token(Token::Period)?;
-            let next_token = self.next_token()?;
-            match next_token.clone() {
-                Token::Identifier(id_name2) => {
-                    self.match_token(next_token)?;
-                    Ok((Some(id_name1), id_name2))
-                }
-                ut => Err(ParseError::InvalidToken(ut).into()),
-            }
-        } else {
-            Ok((None, id_name1))
+        self.match_token(Token::LeftParenthesis)?;
+        let mut columns = vec![self.match_order_by_expression()?];
+        while self.next_token()? == Token::Comma {
+            self.match_token(Token::Comma)?;
+            columns.push(self.match_order_by_expression()?);
+        }
+        self.match_token(Token::RightParenthesis)?;
+
+        let mut include: Vec<String> = Vec::new();
+        if self.peek_word(0, "include") {
+            self.match_word("include")?;
+            include = self.match_column_list()?;
         }
+        let where_expression = self
+            .match_where_expression()
+            .context("failed to match where expression")?;
+
+        Ok(CreateIndexStatement {
+            unique,
+            concurrently,
+            if_not_exists,
+            name,
+            table,
+            method,
+            columns,
+            include,
+            where_expression,
+        })
     }
 
-    fn match_where_expression(&mut self) -> Result<Option<Term>> {
-        self.log("match_where_expression()".to_string());
+    // matches "[OR REPLACE] [MATERIALIZED] VIEW name [(column, ...)] AS query
+    // [WITH [NO] DATA]", following CREATE
+    fn match_create_view(&mut self) -> Result<CreateViewStatement> {
+        self.log("match_create_view()".to_string());
 
-        if self.next_token()? == Token::Where {
-            self.match_token(Token::Where)?;
-            Ok(Some(self.match_expression()?))
-        } else {
-            Ok(None)
+        let mut or_replace = false;
+        if self.next_token()? == Token::Or {
+            self.match_token(Token::Or)?;
+            self.match_word("replace")?;
+            or_replace = true;
+        }
+        let mut materialized = false;
+        if self.peek_word(0, "materialized") {
+            self.match_word("materialized")?;
+            materialized = true;
+        }
+        self.match_word("view")?;
+        let name = self
+            .match_object_name()
+            .context("failed to match view name")?;
+        let mut columns: Vec<String> = Vec::new();
+        if self.next_token()? == Token::LeftParenthesis {
+            columns = self.match_column_list()?;
+        }
+        self.match_token(Token::As)?;
+        let query = self.match_query()?;
+
+        let mut with_data: Option<bool> = None;
+        if materialized && self.next_token()? == Token::With {
+            self.match_token(Token::With)?;
+            let mut data = true;
+            if self.peek_word(0, "no") {
+                self.match_word("no")?;
+                data = false;
+            }
+            self.match_word("data")?;
+            with_data = Some(data);
         }
+
+        Ok(CreateViewStatement {
+            or_replace,
+            materialized,
+            name,
+            columns,
+            query: Box::new(query),
+            with_data,
+        })
     }
 
-    // an expression is a logical statement typically including "AND" and "OR"
-    fn match_expression(&mut self) -> Result<Term> {
-        let mut operands: Vec<Box<Operand>> = Vec::new();
-        let mut operators: Vec<Token> = Vec::new();
-        let mut last_was_term = false;
+    // matches "[TEMPORARY] TABLE [IF NOT EXISTS] name ({column | constraint}, ...)"
+    // or "... name AS query", following CREATE
+    fn match_create_table(&mut self) -> Result<CreateTableStatement> {
+        self.log("match_create_table()".to_string());
+
+        let mut temporary = false;
+        if self.peek_word(0, "temporary") {
+            self.match_word("temporary")?;
+            temporary = true;
+        } else if self.peek_word(0, "temp") {
+            self.match_word("temp")?;
+            temporary = true;
+        }
+        self.match_word("table")?;
+        let if_not_exists = self.match_if_not_exists()?;
+        let name = self
+            .match_object_name()
+            .context("failed to match table name")?;
+
+        let mut columns: Vec<ColumnDefinition> = Vec::new();
+        let mut constraints: Vec<TableConstraint> = Vec::new();
+        let mut query: Option<Box<Query>> = None;
+        if self.next_token()? == Token::As {
+            self.match_token(Token::As)?;
+            query = Some(Box::new(self.match_query()?));
+        } else {
+            self.match_token(Token::LeftParenthesis)?;
+            loop {
+                if self.peek_table_constraint() {
+                    constraints.push(self.match_table_constraint()?);
+                } else {
+                    columns.push(self.match_column_definition()?);
+                }
+                if self.next_token()? != Token::Comma {
+                    break;
+                }
+                self.match_token(Token::Comma)?;
+            }
+            self.match_token(Token::RightParenthesis)?;
+        }
 
-        while self.expression_continues()? {
-            let next_token = &self.next_token()?;
-            println!("next_token: {:?}", next_token);
+        Ok(CreateTableStatement {
+            temporary,
+            if_not_exists,
+            name,
+            columns,
+            constraints,
+            query,
+        })
+    }
 
-            if *next_token == Token::LeftParenthesis {
-                self.match_token(next_token.clone())?;
-                operators.push(next_token.clone());
-                last_was_term = false;
-                continue;
+    // matches "ALTER TABLE [IF EXISTS] name operation, ..."
+    fn match_alter_table(&mut self) -> Result<AlterTableStatement> {
+        self.log("match_alter_table()".to_string());
+
+        self.match_token(Token::Alter)?;
+        self.match_word("table")?;
+        let if_exists = self.match_if_exists()?;
+        let name = self
+            .match_object_name()
+            .context("failed to match table name")?;
+
+        let mut operations: Vec<AlterTableOperation> = Vec::new();
+        loop {
+            operations.push(self.match_alter_table_operation()?);
+            if self.next_token()? != Token::Comma {
+                break;
             }
+            self.match_token(Token::Comma)?;
+        }
 
-            // always match and push a term
-            if !last_was_term {
-                operands.push(Box::new(Operand::Term(self.match_base_term()?)));
-                last_was_term = true;
-                continue;
-            }
+        Ok(AlterTableStatement {
+            if_exists,
+            name,
+            operations,
+        })
+    }
 
-            // handle operator or right parenthesis
-            if next_token.clone().is_expression_operator() {
-                // continue expression
+    fn match_alter_table_operation(&mut self) -> Result<AlterTableOperation> {
+        self.log("match_alter_table_operation()".to_string());
 
-                let number_of_operators = operators
-                    .iter()
-                    .rev()
-                    .take_while(|&token| *token != Token::LeftParenthesis)
-                    .count();
-                let number_of_operands = number_of_operators + 1;
-
-                if let Some(last_operator) = operators.last() {
-                    if Parser::operator_precedence(next_token)
-                        <= Parser::operator_precedence(last_operator)
-                        && number_of_operands > number_of_operators
-                        && operators.len() > 0
-                        && operands.len() >= 2
-                    {
-                        let op1 = operands.remove(operands.len() - 2);
-                        let op2 = operands.remove(operands.len() - 1);
-                        let last_operator_popped = operators.remove(operators.len() - 1);
-                        let compacted_op =
-                            self.apply_operator_to_terms(last_operator_popped, op1, op2)?;
-                        operands.push(compacted_op);
-                    }
+        match self.next_token()? {
+            Token::Drop => {
+                self.match_token(Token::Drop)?;
+                if self.peek_word(0, "constraint") {
+                    self.match_word("constraint")?;
+                    let if_exists = self.match_if_exists()?;
+                    let name = self.match_identifier()?;
+                    let drop_behavior = self.match_drop_behavior()?;
+                    return Ok(AlterTableOperation::DropConstraint {
+                        if_exists,
+                        name,
+                        drop_behavior,
+                    });
                 }
-
-                self.match_token(next_token.clone())?;
-                operators.push(next_token.clone());
-                last_was_term = false;
-            } else if *next_token == Token::RightParenthesis {
-                // end of sub-expression
-                // compact all operands and operators inside of the parentheses
-                self.match_token(next_token.clone())?;
-
-                while operands.len()
-                    > operators
-                        .iter()
-                        .rev()
-                        .take_while(|&token| *token != Token::LeftParenthesis)
-                        .count()
-                    && operators.len() > 0
-                    && operands.len() >= 2
-                {
-                    if let Some(last_operand) = operators.last() {
-                        if *last_operand == Token::LeftParenthesis {
-                            operators.remove(operators.len() - 1);
-                            break;
-                        }
-                    }
-                    let op1 = operands.remove(operands.len() - 2);
-                    let op2 = operands.remove(operands.len() - 1);
-                    let last_operator_popped = operators.remove(operators.len() - 1);
-                    let compacted_op =
-                        self.apply_operator_to_terms(last_operator_popped, op1, op2)?;
-                    operands.push(compacted_op);
+                if self.peek_word(0, "column") {
+                    self.match_word("column")?;
                 }
-                last_was_term = true;
-            } else {
-                return Err(ParseError::NotImplemented(format!(
-                    "expected an expression operator or right parenthesis but found: {:?}",
-                    next_token
-                ))
-                .into());
+                let if_exists = self.match_if_exists()?;
+                let name = self.match_identifier()?;
+                let drop_behavior = self.match_drop_behavior()?;
+                Ok(AlterTableOperation::DropColumn {
+                    if_exists,
+                    name,
+                    drop_behavior,
+                })
             }
-        }
-
-        while operators.len() > 0 && operands.len() >= 2 {
-            if let Some(last_operand) = operators.last() {
-                if *last_operand == Token::LeftParenthesis
-                    || *last_operand == Token::RightParenthesis
-                {
-                    return Err(ParseError::OperandCompactionIssue(format!(
-                        "unexpected parenthesis on final compaction: {:?}",
-                        *last_operand
-                    ))
-                    .into());
+            Token::Alter => {
+                self.match_token(Token::Alter)?;
+                if self.peek_word(0, "column") {
+                    self.match_word("column")?;
                 }
+                let name = self.match_identifier()?;
+                let operation = self.match_alter_column_operation()?;
+                Ok(AlterTableOperation::AlterColumn { name, operation })
             }
-            let op1 = operands.remove(operands.len() - 2);
-            let op2 = operands.remove(operands.len() - 1);
-            let last_operator_popped = operators.remove(operators.len() - 1);
-            let compacted_op = self.apply_operator_to_terms(last_operator_popped, op1, op2)?;
-            operands.push(compacted_op);
+            _ if self.peek_word(0, "add") => {
+                self.match_word("add")?;
+                if self.peek_table_constraint() {
+                    return Ok(AlterTableOperation::AddConstraint(
+                        self.match_table_constraint()?,
+                    ));
+                }
+                if self.peek_word(0, "column") {
+                    self.match_word("column")?;
+                }
+                let if_not_exists = self.match_if_not_exists()?;
+                let column = self.match_column_definition()?;
+                Ok(AlterTableOperation::AddColumn {
+                    if_not_exists,
+                    column,
+                })
+            }
+            _ if self.peek_word(0, "rename") => {
+                self.match_word("rename")?;
+                if self.peek_word(0, "to") {
+                    self.match_word("to")?;
+                    return Ok(AlterTableOperation::RenameTable(self.match_object_name()?));
+                }
+                if self.peek_word(0, "column") {
+                    self.match_word("column")?;
+                }
+                let old_name = self.match_identifier()?;
+                self.match_word("to")?;
+                let new_name = self.match_identifier()?;
+                Ok(AlterTableOperation::RenameColumn { old_name, new_name })
+            }
+            ut => Err(ParseError::InvalidToken(ut).into()),
         }
+    }
 
-        if operands.len() != 1 || operators.len() != 0 {
-            return Err(ParseError::OperandCompactionIssue(format!(
+    // matches what follows "ALTER [COLUMN] name"
+    fn match_alter_column_operation(&mut self) -> Result<AlterColumnOperation> {
+        match self.next_token()? {
+            Token::Set if self.peek_word(1, "data") || self.peek_word(1, "type") => {
+                self.match_token(Token::Set)?;
+                if self.peek_word(0, "data") {
+                    self.match_word("data")?;
+                }
+                self.match_alter_column_type()
+            }
+            Token::Set => {
+                self.match_token(Token::Set)?;
+                if self.next_token()? == Token::Default {
+                    self.match_token(Token::Default)?;
+                    return Ok(AlterColumnOperation::SetDefault(self.match_expression()?));
+                }
+                self.match_token(Token::Not)?;
+                self.match_token(Token::Null)?;
+                Ok(AlterColumnOperation::SetNotNull)
+            }
+            Token::Drop => {
+                self.match_token(Token::Drop)?;
+                if self.next_token()? == Token::Default {
+                    self.match_token(Token::Default)?;
+                    return Ok(AlterColumnOperation::DropDefault);
+                }
+                self.match_token(Token::Not)?;
+                self.match_token(Token::Null)?;
+                Ok(AlterColumnOperation::DropNotNull)
+            }
+            _ => self.match_alter_column_type(),
+        }
+    }
+
+    // matches "TYPE data_type [USING expression]"
+    fn match_alter_column_type(&mut self) -> Result<AlterColumnOperation> {
+        self.match_word("type")?;
+        let data_type = self
+            .match_data_type()
+            .context("failed to match column type")?;
+        let mut using: Option<Term> = None;
+        if self.next_token()? == Token::Using {
+            self.match_token(Token::Using)?;
+            using = Some(self.match_expression()?);
+        }
+        Ok(AlterColumnOperation::SetDataType { data_type, using })
+    }
+
+    // transaction control words aren't reserved, so they are only
+    // recognized at the start of a statement
+    fn match_transaction_statement(&mut self) -> Result<Statement> {
+        self.log("match_transaction_statement()".to_string());
+
+        if self.peek_word(0, "begin") {
+            self.match_word("begin")?;
+            if self.peek_word(0, "transaction") || self.peek_word(0, "work") {
+                self.read_next_token();
+            }
+            return Ok(Statement::StartTransaction(self.match_transaction_modes()?));
+        }
+        if self.peek_word(0, "start") {
+            self.match_word("start")?;
+            self.match_word("transaction")?;
+            return Ok(Statement::StartTransaction(self.match_transaction_modes()?));
+        }
+        if self.peek_word(0, "commit") {
+            self.match_word("commit")?;
+            if self.peek_word(0, "transaction") || self.peek_word(0, "work") {
+                self.read_next_token();
+            }
+            return Ok(Statement::Commit);
+        }
+        if self.peek_word(0, "rollback") {
+            self.match_word("rollback")?;
+            if self.peek_word(0, "transaction") || self.peek_word(0, "work") {
+                self.read_next_token();
+            }
+            let mut savepoint: Option<String> = None;
+            if self.peek_word(0, "to") {
+                self.match_word("to")?;
+                if self.peek_word(0, "savepoint") {
+                    self.match_word("savepoint")?;
+                }
+                savepoint = Some(self.match_identifier()?);
+            }
+            return Ok(Statement::Rollback(savepoint));
+        }
+        if self.peek_word(0, "savepoint") {
+            self.match_word("savepoint")?;
+            return Ok(Statement::Savepoint(self.match_identifier()?));
+        }
+        if self.peek_word(0, "release") {
+            self.match_word("release")?;
+            if self.peek_word(0, "savepoint") {
+                self.match_word("savepoint")?;
+            }
+            return Ok(Statement::ReleaseSavepoint(self.match_identifier()?));
+        }
+        Err(ParseError::InvalidToken(self.next_token()?).into())
+    }
+
+    // matches "[ISOLATION LEVEL level | READ ONLY | READ WRITE] [[,] ...]"
+    fn match_transaction_modes(&mut self) -> Result<Vec<TransactionMode>> {
+        let mut modes: Vec<TransactionMode> = Vec::new();
+        loop {
+            if self.peek_word(0, "isolation") {
+                self.match_word("isolation")?;
+                self.match_word("level")?;
+                let level = if self.peek_word(0, "serializable") {
+                    self.match_word("serializable")?;
+                    IsolationLevel::Serializable
+                } else if self.peek_word(0, "repeatable") {
+                    self.match_word("repeatable")?;
+                    self.match_word("read")?;
+                    IsolationLevel::RepeatableRead
+                } else {
+                    self.match_word("read")?;
+                    if self.peek_word(0, "uncommitted") {
+                        self.match_word("uncommitted")?;
+                        IsolationLevel::ReadUncommitted
+                    } else {
+                        self.match_word("committed")?;
+                        IsolationLevel::ReadCommitted
+                    }
+                };
+                modes.push(TransactionMode::IsolationLevel(level));
+            } else if self.peek_word(0, "read") {
+                self.match_word("read")?;
+                let access_mode = if self.peek_word(0, "only") {
+                    self.match_word("only")?;
+                    TransactionAccessMode::ReadOnly
+                } else {
+                    self.match_word("write")?;
+                    TransactionAccessMode::ReadWrite
+                };
+                modes.push(TransactionMode::AccessMode(access_mode));
+            } else {
+                break;
+            }
+
+            if self.next_token()? == Token::Comma {
+                self.match_token(Token::Comma)?;
+                if !self.peek_word(0, "isolation") && !self.peek_word(0, "read") {
+                    return Err(ParseError::InvalidToken(self.next_token()?).into());
+                }
+            }
+        }
+        Ok(modes)
+    }
+
+    // matches "DROP object_type [IF EXISTS] name, ... [CASCADE | RESTRICT]"
+    fn match_drop(&mut self) -> Result<DropStatement> {
+        self.log("match_drop()".to_string());
+
+        self.match_token(Token::Drop)?;
+        let object_type = self.match_object_type()?;
+        let if_exists = self.match_if_exists()?;
+
+        let mut names: Vec<DropName> = Vec::new();
+        loop {
+            let name = self.match_object_name()?;
+            let mut parameter_types: Option<Vec<DataType>> = None;
+            if object_type == ObjectType::Function && self.next_token()? == Token::LeftParenthesis {
+                self.match_token(Token::LeftParenthesis)?;
+                let mut data_types: Vec<DataType> = Vec::new();
+                // f() names a function without parameters
+                if self.next_token()? != Token::RightParenthesis {
+                    loop {
+                        data_types.push(self.match_data_type()?);
+                        if self.next_token()? != Token::Comma {
+                            break;
+                        }
+                        self.match_token(Token::Comma)?;
+                    }
+                }
+                self.match_token(Token::RightParenthesis)?;
+                parameter_types = Some(data_types);
+            }
+            names.push(DropName {
+                name,
+                parameter_types,
+            });
+
+            if self.next_token()? != Token::Comma {
+                break;
+            }
+            self.match_token(Token::Comma)?;
+        }
+        let drop_behavior = self.match_drop_behavior()?;
+
+        Ok(DropStatement {
+            object_type,
+            if_exists,
+            names,
+            drop_behavior,
+        })
+    }
+
+    fn match_object_type(&mut self) -> Result<ObjectType> {
+        let object_type = match self.next_token()? {
+            Token::Identifier(word) => match word.to_lowercase().as_str() {
+                "table" => ObjectType::Table,
+                "view" => ObjectType::View,
+                "materialized" => {
+                    self.read_next_token();
+                    self.match_word("view")?;
+                    return Ok(ObjectType::MaterializedView);
+                }
+                "index" => ObjectType::Index,
+                "schema" => ObjectType::Schema,
+                "sequence" => ObjectType::Sequence,
+                "type" => ObjectType::Type,
+                "function" => ObjectType::Function,
+                _ => return Err(ParseError::NotImplemented(format!("object type {}", word)).into()),
+            },
+            ut => return Err(ParseError::InvalidToken(ut).into()),
+        };
+        self.read_next_token();
+        Ok(object_type)
+    }
+
+    // matches "TRUNCATE [TABLE] name, ... [RESTART | CONTINUE IDENTITY] [CASCADE | RESTRICT]"
+    fn match_truncate(&mut self) -> Result<TruncateStatement> {
+        self.log("match_truncate()".to_string());
+
+        self.match_token(Token::Truncate)?;
+        if self.peek_word(0, "table") {
+            self.match_word("table")?;
+        }
+
+        let mut names = vec![self.match_object_name()?];
+        while self.next_token()? == Token::Comma {
+            self.match_token(Token::Comma)?;
+            names.push(self.match_object_name()?);
+        }
+
+        let mut restart_identity = false;
+        if self.peek_word(0, "restart") {
+            self.match_word("restart")?;
+            self.match_word("identity")?;
+            restart_identity = true;
+        } else if self.peek_word(0, "continue") {
+            self.match_word("continue")?;
+            self.match_word("identity")?;
+        }
+        let drop_behavior = self.match_drop_behavior()?;
+
+        Ok(TruncateStatement {
+            names,
+            restart_identity,
+            drop_behavior,
+        })
+    }
+
+    // matches an optional "CASCADE" or "RESTRICT"
+    fn match_drop_behavior(&mut self) -> Result<Option<DropBehavior>> {
+        if self.peek_word(0, "cascade") {
+            self.match_word("cascade")?;
+            return Ok(Some(DropBehavior::Cascade));
+        }
+        if self.peek_word(0, "restrict") {
+            self.match_word("restrict")?;
+            return Ok(Some(DropBehavior::Restrict));
+        }
+        Ok(None)
+    }
+
+    fn match_if_exists(&mut self) -> Result<bool> {
+        if !self.peek_word(0, "if") {
+            return Ok(false);
+        }
+        self.match_word("if")?;
+        self.match_token(Token::Exists)?;
+        Ok(true)
+    }
+
+    fn match_if_not_exists(&mut self) -> Result<bool> {
+        if !self.peek_word(0, "if") {
+            return Ok(false);
+        }
+        self.match_word("if")?;
+        self.match_token(Token::Not)?;
+        self.match_token(Token::Exists)?;
+        Ok(true)
+    }
+
+    // matches "name data_type [column_constraint ...]"
+    fn match_column_definition(&mut self) -> Result<ColumnDefinition> {
+        self.log("match_column_definition()".to_string());
+
+        let name = self.match_identifier()?;
+        let data_type = self
+            .match_data_type()
+            .context("failed to match column type")?;
+
+        // the definition ends at the first token that doesn't start a
+        // constraint, a comma or parenthesis in CREATE TABLE
+        let mut constraints: Vec<ColumnConstraint> = Vec::new();
+        loop {
+            let mut constraint_name: Option<String> = None;
+            if self.peek_word(0, "constraint") {
+                self.match_word("constraint")?;
+                constraint_name = Some(self.match_identifier()?);
+            }
+
+            let option = match self.next_token()? {
+                Token::Not => {
+                    self.match_token(Token::Not)?;
+                    self.match_token(Token::Null)?;
+                    ColumnOption::NotNull
+                }
+                Token::Null => {
+                    self.match_token(Token::Null)?;
+                    ColumnOption::Null
+                }
+                Token::Default => {
+                    self.match_token(Token::Default)?;
+                    ColumnOption::Default(self.match_expression()?)
+                }
+                _ if self.peek_word(0, "primary") => {
+                    self.match_word("primary")?;
+                    self.match_word("key")?;
+                    ColumnOption::PrimaryKey
+                }
+                _ if self.peek_word(0, "unique") => {
+                    self.match_word("unique")?;
+                    ColumnOption::Unique
+                }
+                _ if self.peek_word(0, "references") => {
+                    ColumnOption::References(self.match_foreign_key_reference()?)
+                }
+                _ if self.peek_word(0, "check") => {
+                    self.match_word("check")?;
+                    ColumnOption::Check(self.match_parenthesised_expression()?)
+                }
+                _ if self.peek_word(0, "collate") => {
+                    self.match_word("collate")?;
+                    ColumnOption::Collate(self.match_object_name()?)
+                }
+                ut if constraint_name.is_some() => return Err(ParseError::InvalidToken(ut).into()),
+                _ => break,
+            };
+            constraints.push(ColumnConstraint {
+                name: constraint_name,
+                option,
+            });
+        }
+
+        Ok(ColumnDefinition {
+            name,
+            data_type,
+            constraints,
+        })
+    }
+
+    // a table constraint is told apart from a column definition by its
+    // leading words, PRIMARY KEY, UNIQUE (, FOREIGN KEY, CHECK ( or CONSTRAINT
+    fn peek_table_constraint(&mut self) -> bool {
+        self.peek_word(0, "constraint")
+            || (self.peek_word(0, "primary") && self.peek_word(1, "key"))
+            || (self.peek_word(0, "foreign") && self.peek_word(1, "key"))
+            || ((self.peek_word(0, "unique") || self.peek_word(0, "check"))
+                && self.peek_nth_token(1) == Some(&Token::LeftParenthesis))
+    }
+
+    fn match_table_constraint(&mut self) -> Result<TableConstraint> {
+        self.log("match_table_constraint()".to_string());
+
+        let mut name: Option<String> = None;
+        if self.peek_word(0, "constraint") {
+            self.match_word("constraint")?;
+            name = Some(self.match_identifier()?);
+        }
+
+        let kind = if self.peek_word(0, "primary") {
+            self.match_word("primary")?;
+            self.match_word("key")?;
+            TableConstraintKind::PrimaryKey(self.match_column_list()?)
+        } else if self.peek_word(0, "unique") {
+            self.match_word("unique")?;
+            TableConstraintKind::Unique(self.match_column_list()?)
+        } else if self.peek_word(0, "foreign") {
+            self.match_word("foreign")?;
+            self.match_word("key")?;
+            let columns = self.match_column_list()?;
+            let references = self.match_foreign_key_reference()?;
+            TableConstraintKind::ForeignKey {
+                columns,
+                references,
+            }
+        } else {
+            self.match_word("check")?;
+            TableConstraintKind::Check(self.match_parenthesised_expression()?)
+        };
+
+        Ok(TableConstraint { name, kind })
+    }
+
+    // matches "(column, ...)"
+    fn match_column_list(&mut self) -> Result<Vec<String>> {
+        self.match_token(Token::LeftParenthesis)?;
+        let columns = self.match_identifier_list()?;
+        self.match_token(Token::RightParenthesis)?;
+        Ok(columns)
+    }
+
+    fn match_parenthesised_expression(&mut self) -> Result<Term> {
+        self.match_token(Token::LeftParenthesis)?;
+        let expression = self.match_expression()?;
+        self.match_token(Token::RightParenthesis)?;
+        Ok(expression)
+    }
+
+    // matches "REFERENCES table [(column, ...)] [ON {DELETE | UPDATE} action ...]"
+    fn match_foreign_key_reference(&mut self) -> Result<ForeignKeyReference> {
+        self.log("match_foreign_key_reference()".to_string());
+
+        self.match_word("references")?;
+        let table = self.match_object_name()?;
+        let mut columns: Vec<String> = Vec::new();
+        if self.next_token()? == Token::LeftParenthesis {
+            columns = self.match_column_list()?;
+        }
+
+        let mut on_delete: Option<ReferentialAction> = None;
+        let mut on_update: Option<ReferentialAction> = None;
+        while self.next_token()? == Token::On {
+            self.match_token(Token::On)?;
+            if self.next_token()? == Token::Delete {
+                self.match_token(Token::Delete)?;
+                on_delete = Some(self.match_referential_action()?);
+            } else {
+                self.match_token(Token::Update)?;
+                on_update = Some(self.match_referential_action()?);
+            }
+        }
+
+        Ok(ForeignKeyReference {
+            table,
+            columns,
+            on_delete,
+            on_update,
+        })
+    }
+
+    fn match_referential_action(&mut self) -> Result<ReferentialAction> {
+        if self.next_token()? == Token::Set {
+            self.match_token(Token::Set)?;
+            if self.next_token()? == Token::Null {
+                self.match_token(Token::Null)?;
+                return Ok(ReferentialAction::SetNull);
+            }
+            self.match_token(Token::Default)?;
+            return Ok(ReferentialAction::SetDefault);
+        }
+        if self.peek_word(0, "cascade") {
+            self.match_word("cascade")?;
+            return Ok(ReferentialAction::Cascade);
+        }
+        if self.peek_word(0, "restrict") {
+            self.match_word("restrict")?;
+            return Ok(ReferentialAction::Restrict);
+        }
+        self.match_word("no")?;
+        self.match_word("action")?;
+        Ok(ReferentialAction::NoAction)
+    }
+
+    // matches "column = value, (column, ...) = (value, ...), ..." following
+    // SET or MySQL's ON DUPLICATE KEY UPDATE
+    fn match_assignment_list(&mut self) -> Result<Vec<Assignment>> {
+        self.log("match_assignment_list()".to_string());
+
+        let mut assignments: Vec<Assignment> = Vec::new();
+        loop {
+            let assignment = if self.next_token()? == Token::LeftParenthesis {
+                self.match_token(Token::LeftParenthesis)?;
+                let mut columns = vec![self.match_object_name()?];
+                while self.next_token()? == Token::Comma {
+                    self.match_token(Token::Comma)?;
+                    columns.push(self.match_object_name()?);
+                }
+                self.match_token(Token::RightParenthesis)?;
+                self.match_token(Token::Equal)?;
+
+                let value = if self.peek_subquery() {
+                    Term::Subquery(Box::new(self.match_subquery()?))
+                } else {
+                    self.match_token(Token::LeftParenthesis)?;
+                    let mut values = vec![self.match_expression_or_default()?];
+                    while self.next_token()? == Token::Comma {
+                        self.match_token(Token::Comma)?;
+                        values.push(self.match_expression_or_default()?);
+                    }
+                    self.match_token(Token::RightParenthesis)?;
+                    if values.len() != columns.len() {
+                        return Err(ParseError::InvalidArgumentCount(
+                            "tuple assignment".to_string(),
+                            values.len(),
+                        )
+                        .into());
+                    }
+                    Term::Row(values)
+                };
+                Assignment {
+                    target: AssignmentTarget::Tuple(columns),
+                    value,
+                }
+            } else {
+                let column = self.match_object_name()?;
+                self.match_token(Token::Equal)?;
+                Assignment {
+                    target: AssignmentTarget::Column(column),
+                    value: self.match_expression_or_default()?,
+                }
+            };
+            assignments.push(assignment);
+
+            if self.next_token()? != Token::Comma {
+                break;
+            }
+            self.match_token(Token::Comma)?;
+        }
+        Ok(assignments)
+    }
+
+    // DEFAULT is allowed in place of a value in INSERT VALUES lists and SET
+    // clauses
+    fn match_expression_or_default(&mut self) -> Result<Term> {
+        if self.next_token()? == Token::Default {
+            self.match_token(Token::Default)?;
+            return Ok(Term::Default);
+        }
+        self.match_expression()
+    }
+
+    fn match_query(&mut self) -> Result<Query> {
+        self.log("match_query()".to_string());
+
+        let with = self.match_with().context("failed to match with")?;
+        self.match_query_body(with)
+    }
+
+    // matches everything of a query following its WITH clause
+    fn match_query_body(&mut self, with: Option<With>) -> Result<Query> {
+        let body = self.match_set_expression(0)?;
+        let mut order_by: Vec<OrderByExpr> = Vec::new();
+        if self.next_token()? == Token::Order {
+            order_by = self.match_order_by().context("failed to match order by")?;
+        }
+        let limit = self
+            .match_row_limit()
+            .context("failed to match row limit")?;
+        if let SetExpression::Select(select_statement) = &body {
+            if select_statement.top.is_some() && limit.is_some() {
+                return Err(ParseError::NotImplemented(
+                    "TOP combined with another row limit".to_string(),
+                )
+                .into());
+            }
+        }
+
+        Ok(Query {
+            with,
+            body,
+            order_by,
+            limit,
+        })
+    }
+
+    // precedence climbing over set operations, only operators that bind
+    // tighter than the given precedence are matched
+    fn match_set_expression(&mut self, precedence: u8) -> Result<SetExpression> {
+        self.log("match_set_expression()".to_string());
+
+        let mut set_expression = if self.next_token()? == Token::LeftParenthesis {
+            self.match_token(Token::LeftParenthesis)?;
+            let query = self.match_query()?;
+            self.match_token(Token::RightParenthesis)?;
+            SetExpression::Query(Box::new(query))
+        } else if self.next_token()? == Token::Values {
+            SetExpression::Values(self.match_values(false).context("failed to match values")?)
+        } else {
+            SetExpression::Select(Box::new(self.match_select()?))
+        };
+
+        loop {
+            let (operator, operator_precedence) = match self.next_token()? {
+                Token::Union => (SetOperator::Union, 1),
+                Token::Except => (SetOperator::Except, 1),
+                Token::Intersect => (SetOperator::Intersect, 2),
+                _ => break,
+            };
+            if operator_precedence <= precedence {
+                break;
+            }
+            let next_token = self.next_token()?;
+            self.match_token(next_token)?;
+
+            let quantifier = match self.next_token()? {
+                Token::All => {
+                    self.match_token(Token::All)?;
+                    Some(SetQuantifier::All)
+                }
+                Token::Distinct => {
+                    self.match_token(Token::Distinct)?;
+                    Some(SetQuantifier::Distinct)
+                }
+                _ => None,
+            };
+
+            let right = self.match_set_expression(operator_precedence)?;
+            set_expression = SetExpression::SetOperation {
+                operator,
+                quantifier,
+                left: Box::new(set_expression),
+                right: Box::new(right),
+            };
+        }
+
+        Ok(set_expression)
+    }
+
+    fn match_with(&mut self) -> Result<Option<With>> {
+        if self.next_token()? != Token::With {
+            return Ok(None);
+        }
+        self.log("match_with()".to_string());
+
+        self.match_token(Token::With)?;
+        let recursive = self.next_token()? == Token::Recursive;
+        if recursive {
+            self.match_token(Token::Recursive)?;
+        }
+
+        let mut common_table_expressions: Vec<CommonTableExpression> = Vec::new();
+        loop {
+            common_table_expressions.push(self.match_common_table_expression()?);
+            if self.next_token()? != Token::Comma {
+                break;
+            }
+            self.match_token(Token::Comma)?;
+        }
+
+        Ok(Some(With {
+            recursive,
+            common_table_expressions,
+        }))
+    }
+
+    fn match_common_table_expression(&mut self) -> Result<CommonTableExpression> {
+        self.log("match_common_table_expression()".to_string());
+
+        let name = self.match_identifier()?;
+        let mut columns: Vec<String> = Vec::new();
+        if self.next_token()? == Token::LeftParenthesis {
+            self.match_token(Token::LeftParenthesis)?;
+            columns = self.match_identifier_list()?;
+            self.match_token(Token::RightParenthesis)?;
+        }
+        self.match_token(Token::As)?;
+
+        let mut materialized: Option<bool> = None;
+        if self.next_token()? == Token::Not {
+            self.match_token(Token::Not)?;
+            self.match_word("materialized")?;
+            materialized = Some(false);
+        } else if self.peek_word(0, "materialized") {
+            self.match_word("materialized")?;
+            materialized = Some(true);
+        }
+
+        self.match_token(Token::LeftParenthesis)?;
+        let query = self.match_query()?;
+        self.match_token(Token::RightParenthesis)?;
+
+        let mut search: Option<CteSearch> = None;
+        if self.peek_word(0, "search") {
+            self.match_word("search")?;
+            let order = if self.peek_word(0, "depth") {
+                self.match_word("depth")?;
+                SearchOrder::DepthFirst
+            } else {
+                self.match_word("breadth")?;
+                SearchOrder::BreadthFirst
+            };
+            self.match_word("first")?;
+            self.match_token(Token::By)?;
+            let columns = self.match_identifier_list()?;
+            self.match_token(Token::Set)?;
+            let sequence_column = self.match_identifier()?;
+            search = Some(CteSearch {
+                order,
+                columns,
+                sequence_column,
+            });
+        }
+
+        let mut cycle: Option<CteCycle> = None;
+        if self.peek_word(0, "cycle") {
+            self.match_word("cycle")?;
+            let columns = self.match_identifier_list()?;
+            self.match_token(Token::Set)?;
+            let mark_column = self.match_identifier()?;
+            let mut mark_value: Option<Term> = None;
+            let mut default_value: Option<Term> = None;
+            if self.peek_word(0, "to") {
+                self.match_word("to")?;
+                mark_value = Some(self.match_expression()?);
+                self.match_token(Token::Default)?;
+                default_value = Some(self.match_expression()?);
+            }
+            self.match_token(Token::Using)?;
+            let path_column = self.match_identifier()?;
+            cycle = Some(CteCycle {
+                columns,
+                mark_column,
+                mark_value,
+                default_value,
+                path_column,
+            });
+        }
+
+        Ok(CommonTableExpression {
+            name,
+            columns,
+            materialized,
+            query: Box::new(query),
+            search,
+            cycle,
+        })
+    }
+
+    // matches "identifier, ..." with at least one identifier
+    fn match_identifier_list(&mut self) -> Result<Vec<String>> {
+        let mut identifiers = vec![self.match_identifier()?];
+        while self.next_token()? == Token::Comma {
+            self.match_token(Token::Comma)?;
+            identifiers.push(self.match_identifier()?);
+        }
+        Ok(identifiers)
+    }
+
+    // matches "VALUES (expression, ...), ..."
+    // DEFAULT is only a valid value when the VALUES list is the source of an
+    // INSERT or MERGE, anywhere else it has no column to take a default from
+    fn match_values(&mut self, allow_default: bool) -> Result<Vec<Vec<Term>>> {
+        self.log("match_values()".to_string());
+
+        self.match_token(Token::Values)?;
+        let mut rows: Vec<Vec<Term>> = Vec::new();
+        loop {
+            self.match_token(Token::LeftParenthesis)?;
+            let mut row: Vec<Term> = Vec::new();
+            loop {
+                if allow_default {
+                    row.push(self.match_expression_or_default()?);
+                } else {
+                    row.push(self.match_expression()?);
+                }
+                if self.next_token()? != Token::Comma {
+                    break;
+                }
+                self.match_token(Token::Comma)?;
+            }
+            self.match_token(Token::RightParenthesis)?;
+            // every row must have as many values as the first one
+            if let Some(first_row) = rows.first() {
+                if row.len() != first_row.len() {
+                    return Err(ParseError::InvalidArgumentCount(
+                        "values row".to_string(),
+                        row.len(),
+                    )
+                    .into());
+                }
+            }
+            rows.push(row);
+
+            if self.next_token()? != Token::Comma {
+                break;
+            }
+            self.match_token(Token::Comma)?;
+        }
+        Ok(rows)
+    }
+
+    fn match_select(&mut self) -> Result<SelectStatement> {
+        self.log("match_select()".to_string());
+
+        self.match_token(Token::Select)?;
+
+        let set_quantifier = self
+            .match_set_quantifier()
+            .context("failed to match set quantifier")?;
+        let top = self.match_top().context("failed to match top")?;
+        let select_expressions = self
+            .match_select_expressions()
+            .context("failed to match select expressions")?;
+        let from_expressions = self
+            .match_from_expressions()
+            .context("failed to match from expressions")?;
+        let where_expression = self
+            .match_where_expression()
+            .context("failed to match where expression")?;
+        let group_by = self.match_group_by().context("failed to match group by")?;
+        let mut having: Option<Term> = None;
+        if self.next_token()? == Token::Having {
+            self.match_token(Token::Having)?;
+            having = Some(self.match_expression().context("failed to match having")?);
+        }
+        let named_windows = self
+            .match_named_windows()
+            .context("failed to match named windows")?;
+
+        let select_statement = SelectStatement {
+            set_quantifier,
+            top,
+            select_expressions,
+            from_expressions,
+            where_expression,
+            group_by,
+            having,
+            named_windows,
+        };
+
+        Ok(select_statement)
+    }
+
+    fn match_set_quantifier(&mut self) -> Result<Option<SetQuantifier>> {
+        match self.next_token()? {
+            Token::All => {
+                self.match_token(Token::All)?;
+                Ok(Some(SetQuantifier::All))
+            }
+            Token::Distinct => {
+                self.match_token(Token::Distinct)?;
+                if self.next_token()? != Token::On {
+                    return Ok(Some(SetQuantifier::Distinct));
+                }
+
+                self.match_token(Token::On)?;
+                self.match_token(Token::LeftParenthesis)?;
+                let mut expressions: Vec<Term> = Vec::new();
+                loop {
+                    expressions.push(self.match_expression()?);
+                    if self.next_token()? != Token::Comma {
+                        break;
+                    }
+                    self.match_token(Token::Comma)?;
+                }
+                self.match_token(Token::RightParenthesis)?;
+                Ok(Some(SetQuantifier::DistinctOn(expressions)))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    // matches the T-SQL "TOP (n) [PERCENT] [WITH TIES]" at the start of a select list
+    fn match_top(&mut self) -> Result<Option<RowLimit>> {
+        if !self.peek_word(0, "top")
+            || !matches!(
+                self.peek_nth_token(1),
+                Some(Token::LeftParenthesis) | Some(Token::Number(_))
+            )
+        {
+            return Ok(None);
+        }
+        self.log("match_top()".to_string());
+        self.match_word("top")?;
+
+        let count = if self.next_token()? == Token::LeftParenthesis {
+            self.match_token(Token::LeftParenthesis)?;
+            let count = self.match_expression()?;
+            self.match_token(Token::RightParenthesis)?;
+            count
+        } else {
+            // wrap the bare count the same way a parsed expression is wrapped
+            Term::Operand(Box::new(Operand::Term(self.match_base_term()?)))
+        };
+
+        let mut percent = false;
+        if self.peek_word(0, "percent") {
+            self.match_word("percent")?;
+            percent = true;
+        }
+
+        let with_ties = self.match_with_ties()?;
+
+        Ok(Some(RowLimit {
+            count: Some(count),
+            offset: None,
+            percent,
+            with_ties,
+        }))
+    }
+
+    // matches any of
+    //   LIMIT { count | ALL } [OFFSET offset]
+    //   LIMIT offset, count
+    //   OFFSET offset [ROW | ROWS] [LIMIT count]
+    //   [OFFSET offset [ROW | ROWS]] FETCH { FIRST | NEXT } [count] { ROW | ROWS } { ONLY | WITH TIES }
+    fn match_row_limit(&mut self) -> Result<Option<RowLimit>> {
+        self.log("match_row_limit()".to_string());
+
+        let mut row_limit = RowLimit {
+            count: None,
+            offset: None,
+            percent: false,
+            with_ties: false,
+        };
+
+        match self.next_token()? {
+            Token::Limit => {
+                self.match_token(Token::Limit)?;
+                if self.next_token()? == Token::All {
+                    self.match_token(Token::All)?;
+                } else {
+                    row_limit.count = Some(self.match_expression()?);
+                }
+
+                if self.next_token()? == Token::Comma {
+                    self.match_token(Token::Comma)?;
+                    row_limit.offset = row_limit.count.take();
+                    row_limit.count = Some(self.match_expression()?);
+                } else if self.next_token()? == Token::Offset {
+                    row_limit.offset = Some(self.match_offset()?);
+                }
+            }
+            Token::Offset => {
+                row_limit.offset = Some(self.match_offset()?);
+
+                if self.next_token()? == Token::Limit {
+                    self.match_token(Token::Limit)?;
+                    row_limit.count = Some(self.match_expression()?);
+                } else if self.next_token()? == Token::Fetch {
+                    self.match_fetch(&mut row_limit)?;
+                }
+            }
+            Token::Fetch => {
+                self.match_fetch(&mut row_limit)?;
+            }
+            _ => return Ok(None),
+        }
+
+        Ok(Some(row_limit))
+    }
+
+    fn match_offset(&mut self) -> Result<Term> {
+        self.match_token(Token::Offset)?;
+        let offset = self.match_expression()?;
+        if self.peek_word(0, "rows") {
+            self.match_word("rows")?;
+        } else if self.peek_word(0, "row") {
+            self.match_word("row")?;
+        }
+        Ok(offset)
+    }
+
+    fn match_fetch(&mut self, row_limit: &mut RowLimit) -> Result<()> {
+        self.match_token(Token::Fetch)?;
+        if self.peek_word(0, "next") {
+            self.match_word("next")?;
+        } else {
+            self.match_word("first")?;
+        }
+
+        // the count defaults to one row when it is left out
+        if !self.peek_word(0, "row") && !self.peek_word(0, "rows") {
+            row_limit.count = Some(self.match_expression()?);
+        } else {
+            row_limit.count = Some(Term::Operand(Box::new(Operand::Term(Term::Value(
+                Value::Numeric(Numeric::Int(1)),
+            )))));
+        }
+        if self.peek_word(0, "percent") {
+            self.match_word("percent")?;
+            row_limit.percent = true;
+        }
+        if self.peek_word(0, "rows") {
+            self.match_word("rows")?;
+        } else {
+            self.match_word("row")?;
+        }
+
+        row_limit.with_ties = self.match_with_ties()?;
+        if !row_limit.with_ties {
+            self.match_word("only")?;
+        }
+        Ok(())
+    }
+
+    fn match_with_ties(&mut self) -> Result<bool> {
+        if self.next_token()? == Token::With && self.peek_word(1, "ties") {
+            self.match_token(Token::With)?;
+            self.match_word("ties")?;
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    fn match_select_expressions(&mut self) -> Result<Vec<SelectExpression>> {
+        self.log("match_select_expressions()".to_string());
+
+        let mut select_expressions: Vec<SelectExpression> = Vec::new();
+        if self.next_token()? == Token::From {
+            return Ok(select_expressions);
+        }
+
+        loop {
+            if self.next_token()? == Token::Star {
+                select_expressions.push(SelectExpression::Star);
+                self.match_token(Token::Star)?;
+            } else if self.peek_qualified_wildcard() {
+                let name = self.match_object_name()?;
+                self.match_token(Token::Period)?;
+                self.match_token(Token::Star)?;
+                select_expressions.push(SelectExpression::Family { name });
+            } else {
+                let expression = self.match_expression()?;
+                let alias = self.match_alias()?;
+                select_expressions.push(SelectExpression::Expression { expression, alias });
+            }
+
+            if self.next_token()? != Token::Comma {
+                break;
+            }
+            self.match_token(Token::Comma)?;
+            if self.next_token()? == Token::From {
+                return Err(ParseError::InvalidToken(Token::From).into());
+            }
+        }
+
+        Ok(select_expressions)
+    }
+
+    fn match_from_expressions(&mut self) -> Result<Vec<TableExpression>> {
+        self.log("match_from_expressions()".to_string());
+
+        let mut from_expressions: Vec<TableExpression> = Vec::new();
+        if self.next_token()? != Token::From {
+            return Ok(from_expressions);
+        }
+        self.match_token(Token::From)?;
+
+        loop {
+            from_expressions.push(self.match_table_expression()?);
+            if self.next_token()? != Token::Comma {
+                break;
+            }
+            self.match_token(Token::Comma)?;
+        }
+        Ok(from_expressions)
+    }
+
+    // matches a table followed by any number of joins
+    fn match_table_expression(&mut self) -> Result<TableExpression> {
+        self.log("match_table_expression()".to_string());
+
+        let mut table_expression = self.match_table_factor()?;
+
+        while let Some((join_type, natural)) = self.match_join_operator()? {
+            table_expression = self.match_join(table_expression, join_type, natural)?;
+        }
+
+        Ok(table_expression)
+    }
+
+    // matches the right side and the constraint of a join after its operator
+    fn match_join(
+        &mut self,
+        left: TableExpression,
+        join_type: JoinType,
+        natural: bool,
+    ) -> Result<TableExpression> {
+        let mut right = self.match_table_factor()?;
+
+        let constraint = if natural {
+            JoinConstraint::Natural
+        } else if matches!(
+            join_type,
+            JoinType::Cross | JoinType::CrossApply | JoinType::OuterApply
+        ) {
+            JoinConstraint::None
+        } else {
+            // a join before the ON or USING clause belongs to the right side,
+            // so a JOIN b JOIN c ON c.x = b.x ON b.y = a.y is a JOIN (b JOIN c)
+            while !matches!(self.next_token()?, Token::On | Token::Using) {
+                match self.match_join_operator()? {
+                    Some((join_type, natural)) => {
+                        right = self.match_join(right, join_type, natural)?;
+                    }
+                    None => break,
+                }
+            }
+
+            if self.next_token()? == Token::On {
+                self.match_token(Token::On)?;
+                JoinConstraint::On(self.match_expression()?)
+            } else {
+                self.match_token(Token::Using)?;
+                self.match_token(Token::LeftParenthesis)?;
+                let columns = self.match_identifier_list()?;
+                self.match_token(Token::RightParenthesis)?;
+                JoinConstraint::Using(columns)
+            }
+        };
+
+        Ok(TableExpression::Join {
+            left: Box::new(left),
+            right: Box::new(right),
+            join_type,
+            constraint,
+        })
+    }
+
+    // matches "[NATURAL] [INNER | LEFT | RIGHT | FULL [OUTER] | CROSS] JOIN"
+    // as well as "CROSS APPLY" and "OUTER APPLY"
+    fn match_join_operator(&mut self) -> Result<Option<(JoinType, bool)>> {
+        let natural = self.next_token()? == Token::Natural;
+        if natural {
+            self.match_token(Token::Natural)?;
+        }
+
+        let join_type = match self.next_token()? {
+            Token::Join => JoinType::Inner,
+            Token::Inner => {
+                self.match_token(Token::Inner)?;
+                JoinType::Inner
+            }
+            Token::Left | Token::Right | Token::Full => {
+                let next_token = self.next_token()?;
+                self.match_token(next_token.clone())?;
+                if self.next_token()? == Token::Outer {
+                    self.match_token(Token::Outer)?;
+                }
+                match next_token {
+                    Token::Left => JoinType::Left,
+                    Token::Right => JoinType::Right,
+                    _ => JoinType::Full,
+                }
+            }
+            Token::Cross if !natural => {
+                self.match_token(Token::Cross)?;
+                if self.peek_word(0, "apply") {
+                    self.match_word("apply")?;
+                    return Ok(Some((JoinType::CrossApply, false)));
+                }
+                JoinType::Cross
+            }
+            Token::Outer if !natural && self.peek_word(1, "apply") => {
+                self.match_token(Token::Outer)?;
+                self.match_word("apply")?;
+                return Ok(Some((JoinType::OuterApply, false)));
+            }
+            ut => {
+                if natural {
+                    return Err(ParseError::InvalidNextToken(Token::Join, ut).into());
+                }
+                return Ok(None);
+            }
+        };
+        self.match_token(Token::Join)?;
+
+        Ok(Some((join_type, natural)))
+    }
+
+    // matches a single table, subquery or parenthesised join
+    fn match_table_factor(&mut self) -> Result<TableExpression> {
+        self.log("match_table_factor()".to_string());
+
+        // LATERAL is not reserved, it is only a keyword in front of a
+        // subquery or a function call
+        let lateral = self.peek_word(0, "lateral")
+            && (self.peek_match_token_types(vec![
+                Token::Identifier(String::new()),
+                Token::LeftParenthesis,
+            ]) || self.peek_match_token_types(vec![
+                Token::Identifier(String::new()),
+                Token::Identifier(String::new()),
+                Token::LeftParenthesis,
+            ]));
+        if lateral {
+            self.match_word("lateral")?;
+            if self.next_token()? == Token::LeftParenthesis && !self.peek_subquery() {
+                return Err(ParseError::NotImplemented(
+                    "LATERAL must be followed by a subquery or a function".to_string(),
+                )
+                .into());
+            }
+        }
+
+        if self.next_token()? == Token::LeftParenthesis && !self.peek_subquery() {
+            self.match_token(Token::LeftParenthesis)?;
+            let table_expression = self.match_table_expression()?;
+            self.match_token(Token::RightParenthesis)?;
+            Ok(TableExpression::Nested(Box::new(table_expression)))
+        } else if self.next_token()? == Token::LeftParenthesis {
+            let query = self.match_subquery()?;
+            let alias = self.match_table_alias()?;
+
+            Ok(TableExpression::Select {
+                lateral,
+                query: Box::new(query),
+                alias,
+            })
+        } else if let Token::Identifier(_) = self.next_token()? {
+            let name = self
+                .match_object_name()
+                .context("failed to match table name")?;
+            if self.next_token()? == Token::LeftParenthesis {
+                return self.match_table_function(lateral, name);
+            }
+            let alias = self.match_table_alias()?;
+            Ok(TableExpression::Table { name, alias })
+        } else {
+            Err(
+                ParseError::NotImplemented("table expression type not implemented".to_string())
+                    .into(),
+            )
+        }
+    }
+
+    // matches "function(...) [WITH ORDINALITY] [alias]" and the special
+    // "UNNEST(array, ...)" form in a FROM clause
+    fn match_table_function(&mut self, lateral: bool, name: ObjectName) -> Result<TableExpression> {
+        self.log("match_table_function()".to_string());
+
+        if name.0.len() == 1 && name.0[0].eq_ignore_ascii_case("unnest") {
+            self.match_token(Token::LeftParenthesis)?;
+            let mut expressions: Vec<Term> = Vec::new();
+            loop {
+                expressions.push(self.match_expression()?);
+                if self.next_token()? != Token::Comma {
+                    break;
+                }
+                self.match_token(Token::Comma)?;
+            }
+            self.match_token(Token::RightParenthesis)?;
+            let with_ordinality = self.match_with_ordinality()?;
+            let alias = self.match_table_alias()?;
+
+            return Ok(TableExpression::Unnest {
+                lateral,
+                expressions,
+                with_ordinality,
+                alias,
+            });
+        }
+
+        let function = self
+            .match_function(name)
+            .context("failed to match table function")?;
+        let with_ordinality = self.match_with_ordinality()?;
+        let alias = self.match_table_alias()?;
+
+        Ok(TableExpression::Function {
+            lateral,
+            function,
+            with_ordinality,
+            alias,
+        })
+    }
+
+    fn match_with_ordinality(&mut self) -> Result<bool> {
+        if self.next_token()? == Token::With && self.peek_word(1, "ordinality") {
+            self.match_token(Token::With)?;
+            self.match_word("ordinality")?;
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    // matches "[AS] alias", keywords are never lexed as identifiers so a
+    // following clause like WHERE can't be mistaken for an implicit alias
+    fn match_alias(&mut self) -> Result<Option<String>> {
+        if self.next_token()? == Token::As {
+            self.match_token(Token::As)?;
+            return Ok(Some(self.match_identifier()?));
+        }
+        if let Token::Identifier(_) = self.next_token()? {
+            return Ok(Some(self.match_identifier()?));
+        }
+        Ok(None)
+    }
+
+    // matches "[AS] alias [(column, ...)]"
+    fn match_table_alias(&mut self) -> Result<Option<TableAlias>> {
+        let Some(name) = self.match_alias()? else {
+            return Ok(None);
+        };
+
+        let mut columns: Vec<String> = Vec::new();
+        if self.next_token()? == Token::LeftParenthesis {
+            self.match_token(Token::LeftParenthesis)?;
+            columns = self.match_identifier_list()?;
+            self.match_token(Token::RightParenthesis)?;
+        }
+
+        Ok(Some(TableAlias { name, columns }))
+    }
+
+    // matches a dot separated name, stopping before a trailing ".*"
+    fn match_object_name(&mut self) -> Result<ObjectName> {
+        self.log("match_object_name()".to_string());
+
+        let mut parts = vec![self.match_identifier()?];
+        while self.peek_match_token_types(vec![Token::Period, Token::Identifier("".to_string())]) {
+            self.match_token(Token::Period)?;
+            parts.push(self.match_identifier()?);
+        }
+        Ok(ObjectName(parts))
+    }
+
+    // peeks for "qualifier.*" where the qualifier can have any number of parts
+    fn peek_qualified_wildcard(&self) -> bool {
+        let mut n = 0;
+        loop {
+            match self.peek_nth_token(n) {
+                Some(Token::Identifier(_)) => {}
+                _ => return false,
+            }
+            if self.peek_nth_token(n + 1) != Some(&Token::Period) {
+                return false;
+            }
+            if self.peek_nth_token(n + 2) == Some(&Token::Star) {
+                return true;
+            }
+            n += 2;
+        }
+    }
+
+    fn match_where_expression(&mut self) -> Result<Option<Term>> {
+        self.log("match_where_expression()".to_string());
+
+        if self.next_token()? == Token::Where {
+            self.match_token(Token::Where)?;
+            Ok(Some(self.match_expression()?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    // an expression is a logical statement typically including "AND" and "OR"
+    fn match_expression(&mut self) -> Result<Term> {
+        let mut operands: Vec<Operand> = Vec::new();
+        let mut operators: Vec<Token> = Vec::new();
+        let mut last_was_term = false;
+
+        while self.expression_continues(last_was_term, &operators)? {
+            let next_token = &self.next_token()?;
+            self.log(format!("next_token: {:?}", next_token));
+
+            if !last_was_term {
+                // a parenthesis starting a subquery is a term rather than a group
+                if *next_token == Token::LeftParenthesis && !self.peek_subquery() {
+                    self.match_token(next_token.clone())?;
+                    operators.push(next_token.clone());
+                    continue;
+                }
+
+                operands.push(Operand::Term(self.match_base_term()?));
+                last_was_term = true;
+                continue;
+            }
+
+            // handle postfix operator, operator or right parenthesis
+            if *next_token == Token::DoubleColon {
+                // the postfix cast binds tighter than any other operator so it
+                // applies to the term or parenthesised expression before it
+                self.match_token(Token::DoubleColon)?;
+                let operand = operands.pop().ok_or(ParseError::OperandCompactionIssue(
+                    "no operand to cast".to_string(),
+                ))?;
+                let data_type = self.match_data_type()?;
+                operands.push(Operand::Term(Term::Cast {
+                    kind: CastKind::DoubleColon,
+                    expression: Box::new(Parser::operand_to_term(operand)),
+                    data_type,
+                }));
+            } else if next_token.is_expression_operator() {
+                // continue expression
+                // compact every operator on the stack that binds at least as
+                // tightly as this one so operators are left associative
+                while let Some(last_operator) = operators.last() {
+                    if *last_operator == Token::LeftParenthesis
+                        || Parser::operator_precedence(next_token)
+                            > Parser::operator_precedence(last_operator)
+                    {
+                        break;
+                    }
+                    self.compact_last_operator(&mut operands, &mut operators)?;
+                }
+
+                self.match_token(next_token.clone())?;
+                operators.push(next_token.clone());
+                last_was_term = false;
+            } else if *next_token == Token::RightParenthesis {
+                // end of sub-expression
+                // compact all operands and operators inside of the parentheses
+                self.match_token(next_token.clone())?;
+
+                while let Some(last_operator) = operators.last() {
+                    if *last_operator == Token::LeftParenthesis {
+                        operators.pop();
+                        break;
+                    }
+                    self.compact_last_operator(&mut operands, &mut operators)?;
+                }
+            } else {
+                return Err(ParseError::NotImplemented(format!(
+                    "expected an expression operator or right parenthesis but found: {:?}",
+                    next_token
+                ))
+                .into());
+            }
+        }
+
+        while let Some(last_operator) = operators.last() {
+            if *last_operator == Token::LeftParenthesis || *last_operator == Token::RightParenthesis
+            {
+                return Err(ParseError::OperandCompactionIssue(format!(
+                    "unexpected parenthesis on final compaction: {:?}",
+                    *last_operator
+                ))
+                .into());
+            }
+            self.compact_last_operator(&mut operands, &mut operators)?;
+        }
+
+        if operands.len() != 1 || !operators.is_empty() {
+            return Err(ParseError::OperandCompactionIssue(format!(
                 "expected to have 1 operand but have {} operands and {} operators",
                 operands.len(),
                 operators.len()
@@ -411,7 +2335,35 @@ impl Parser {
         }
 
         let last_operand = operands.remove(0);
-        Ok(Term::Operand(last_operand))
+        Ok(Term::Operand(Box::new(last_operand)))
+    }
+
+    fn compact_last_operator(
+        &mut self,
+        operands: &mut Vec<Operand>,
+        operators: &mut Vec<Token>,
+    ) -> Result<()> {
+        if operands.len() < 2 {
+            return Err(ParseError::OperandCompactionIssue(format!(
+                "expected at least 2 operands but have {}",
+                operands.len()
+            ))
+            .into());
+        }
+        let op2 = operands.remove(operands.len() - 1);
+        let op1 = operands.remove(operands.len() - 1);
+        let last_operator_popped = operators.remove(operators.len() - 1);
+        let compacted_op =
+            self.apply_operator_to_terms(last_operator_popped, Box::new(op1), Box::new(op2))?;
+        operands.push(*compacted_op);
+        Ok(())
+    }
+
+    fn operand_to_term(operand: Operand) -> Term {
+        match operand {
+            Operand::Term(term) => term,
+            operand => Term::Operand(Box::new(operand)),
+        }
     }
 
     fn apply_operator_to_terms(
@@ -420,7 +2372,7 @@ impl Parser {
         left_operand: Box<Operand>,
         right_operand: Box<Operand>,
     ) -> Result<Box<Operand>> {
-        self.log(format!("apply_operator_to_terms()"));
+        self.log("apply_operator_to_terms()".to_string());
         self.log(format!("- token: {:?}", token));
         self.log(format!("- left_operand: {:?}", left_operand));
         self.log(format!("- right_operand: {:?}", right_operand));
@@ -469,48 +2421,101 @@ impl Parser {
     fn match_base_term(&mut self) -> Result<Term> {
         let next_token = self.next_token()?;
 
+        if next_token == Token::Cast {
+            self.match_token(Token::Cast)?;
+            return self.match_cast(CastKind::Cast);
+        }
+        if self.peek_subquery() {
+            let subquery = self.match_subquery()?;
+            return Ok(Term::Subquery(Box::new(subquery)));
+        }
+        if next_token == Token::Exists
+            || self.peek_match_token_types(vec![Token::Not, Token::Exists])
+        {
+            let negated = next_token == Token::Not;
+            if negated {
+                self.match_token(Token::Not)?;
+            }
+            self.match_token(Token::Exists)?;
+            let subquery = self.match_subquery()?;
+            return Ok(Term::Exists {
+                negated,
+                subquery: Box::new(subquery),
+            });
+        }
+        if let Some(quantifier) = match next_token {
+            Token::All => Some(Quantifier::All),
+            Token::Any => Some(Quantifier::Any),
+            Token::Some => Some(Quantifier::Some),
+            _ => None,
+        } {
+            self.match_token(next_token)?;
+            let expression = if self.peek_subquery() {
+                Term::Subquery(Box::new(self.match_subquery()?))
+            } else {
+                self.match_token(Token::LeftParenthesis)?;
+                let expression = self.match_expression()?;
+                self.match_token(Token::RightParenthesis)?;
+                expression
+            };
+            return Ok(Term::Quantified {
+                quantifier,
+                expression: Box::new(expression),
+            });
+        }
+        if next_token == Token::Array {
+            self.match_token(Token::Array)?;
+            self.match_token(Token::LeftBracket)?;
+            let mut elements: Vec<Term> = Vec::new();
+            if self.next_token()? != Token::RightBracket {
+                loop {
+                    elements.push(self.match_expression()?);
+                    if self.next_token()? != Token::Comma {
+                        break;
+                    }
+                    self.match_token(Token::Comma)?;
+                }
+            }
+            self.match_token(Token::RightBracket)?;
+            return Ok(Term::Array(elements));
+        }
         if self.peek_match_token_types(vec![
             Token::Identifier("".to_string()),
             Token::LeftParenthesis,
         ]) {
-            let id_name = match self.next_token()? {
-                Token::Identifier(name) => name,
-                ut => return Err(ParseError::InvalidToken(ut).into()),
-            };
-            let mut expressions: Vec<Term> = Vec::new();
-
-            self.match_token(next_token)?;
-            self.match_token(Token::LeftParenthesis)?;
-
-            if self.next_token()? == Token::RightParenthesis {
-                return Ok(Term::Function(Function::UserDefined {
-                    name: id_name,
-                    terms: vec![],
-                }));
+            if self.peek_word(0, "try_cast") {
+                self.match_word("try_cast")?;
+                return self.match_cast(CastKind::TryCast);
             }
-
-            // iterate until the end of the function call
-            while self.next_token()? != Token::RightParenthesis {
-                let expression = self.match_expression()?;
-                expressions.push(expression);
-                if self.next_token()? == Token::Comma {
-                    self.match_token(Token::Comma)?;
-                }
+            if self.peek_word(0, "safe_cast") {
+                self.match_word("safe_cast")?;
+                return self.match_cast(CastKind::SafeCast);
             }
-
-            return Ok(Term::Function(Function::UserDefined {
-                name: id_name,
-                terms: expressions,
-            }));
         }
 
-        match self.next_token()? {
+        match next_token {
+            // LEFT and RIGHT are join keywords but also string functions,
+            // VALUES(column) refers to the inserted row in MySQL upserts
+            Token::Left | Token::Right | Token::Values
+                if self.peek_nth_token(1) == Some(&Token::LeftParenthesis) =>
+            {
+                self.match_token(next_token.clone())?;
+                let name = match next_token {
+                    Token::Left => "left",
+                    Token::Right => "right",
+                    _ => "values",
+                };
+                Ok(Term::Function(
+                    self.match_function(ObjectName(vec![name.to_string()]))?,
+                ))
+            }
             Token::Identifier(_) => {
-                let (schema, name) = self.match_table_name()?;
-                Ok(Term::Column(Column::Direct {
-                    schema,
-                    column_name: name,
-                }))
+                let name = self.match_object_name()?;
+                if self.next_token()? == Token::LeftParenthesis {
+                    Ok(Term::Function(self.match_function(name)?))
+                } else {
+                    Ok(Term::Column(Column::Direct { name }))
+                }
             }
             Token::Number(ref value) => {
                 self.match_token(Token::Number(value.clone()))?;
@@ -522,17 +2527,749 @@ impl Parser {
                     Err(ParseError::InvalidNumber(value.clone()).into())
                 }
             }
+            Token::StringToken(ref value) => {
+                self.match_token(next_token.clone())?;
+                Ok(Term::Value(Value::String(value.clone())))
+            }
+            Token::True | Token::False => {
+                self.match_token(next_token.clone())?;
+                Ok(Term::Value(Value::Boolean(next_token == Token::True)))
+            }
+            Token::Null => {
+                self.match_token(Token::Null)?;
+                Ok(Term::Value(Value::Null))
+            }
+            Token::QuestionMark => {
+                self.match_token(Token::QuestionMark)?;
+                Ok(Term::BindParameter(BindParameter::Anonymous))
+            }
+            Token::Dollar => {
+                self.match_token(Token::Dollar)?;
+                Ok(Term::BindParameter(BindParameter::Positional(
+                    self.match_unsigned_integer()?,
+                )))
+            }
+            Token::Colon => {
+                self.match_token(Token::Colon)?;
+                if let Token::Number(_) = self.next_token()? {
+                    Ok(Term::BindParameter(BindParameter::Positional(
+                        self.match_unsigned_integer()?,
+                    )))
+                } else {
+                    Ok(Term::BindParameter(BindParameter::Named(
+                        self.match_identifier()?,
+                    )))
+                }
+            }
             _ => Err(ParseError::NotImplemented("match_term".to_string()).into()),
         }
     }
 
-    fn expression_continues(&mut self) -> Result<bool> {
-        Ok(self.next_token()?.is_expression_operator()
-            || self.peek_match_token_types(vec![
-                Token::Identifier("".to_string()),
-                Token::LeftParenthesis,
-            ])
-            || self.peek_match_token_types(vec![Token::Identifier("".to_string())])
-            || self.peek_match_token_types(vec![Token::Number("".to_string())]))
+    // matches a function call after its name
+    fn match_function(&mut self, name: ObjectName) -> Result<Function> {
+        self.log("match_function()".to_string());
+
+        self.match_token(Token::LeftParenthesis)?;
+
+        let function = if let Some(aggregate_name) = Parser::aggregate_function_name(&name) {
+            self.match_aggregate(aggregate_name)?
+        } else if Parser::is_grouping_function(&name) {
+            let mut expressions: Vec<Term> = Vec::new();
+            loop {
+                expressions.push(self.match_expression()?);
+                if self.next_token()? != Token::Comma {
+                    break;
+                }
+                self.match_token(Token::Comma)?;
+            }
+            self.match_token(Token::RightParenthesis)?;
+            Function::Grouping(expressions)
+        } else {
+            let mut expressions: Vec<Term> = Vec::new();
+
+            // f() is a call without arguments
+            if self.next_token()? != Token::RightParenthesis {
+                loop {
+                    expressions.push(self.match_expression()?);
+                    if self.next_token()? != Token::Comma {
+                        break;
+                    }
+                    self.match_token(Token::Comma)?;
+                }
+            }
+            self.match_token(Token::RightParenthesis)?;
+
+            Function::UserDefined {
+                name,
+                terms: expressions,
+            }
+        };
+
+        if self.next_token()? == Token::Over {
+            self.match_token(Token::Over)?;
+            let window = if self.next_token()? == Token::LeftParenthesis {
+                Window::Specification(self.match_window_specification()?)
+            } else {
+                Window::Named(self.match_identifier()?)
+            };
+            return Ok(Function::Window {
+                function: Box::new(function),
+                window,
+            });
+        }
+
+        Ok(function)
+    }
+
+    fn match_group_by(&mut self) -> Result<Option<GroupBy>> {
+        if self.next_token()? != Token::Group {
+            return Ok(None);
+        }
+        self.log("match_group_by()".to_string());
+
+        self.match_token(Token::Group)?;
+        self.match_token(Token::By)?;
+
+        if self.next_token()? == Token::All {
+            self.match_token(Token::All)?;
+            return Ok(Some(GroupBy::All));
+        }
+
+        let mut elements: Vec<GroupingElement> = Vec::new();
+        loop {
+            elements.push(self.match_grouping_element(false)?);
+            if self.next_token()? != Token::Comma {
+                break;
+            }
+            self.match_token(Token::Comma)?;
+        }
+        Ok(Some(GroupBy::Elements(elements)))
+    }
+
+    // nested is true inside of ROLLUP, CUBE and GROUPING SETS where a
+    // parenthesised single expression is a set rather than a grouped expression
+    fn match_grouping_element(&mut self, nested: bool) -> Result<GroupingElement> {
+        self.log("match_grouping_element()".to_string());
+
+        if self.peek_word(0, "rollup") && self.peek_nth_token(1) == Some(&Token::LeftParenthesis) {
+            self.match_word("rollup")?;
+            return Ok(GroupingElement::Rollup(self.match_grouping_elements()?));
+        }
+        if self.peek_word(0, "cube") && self.peek_nth_token(1) == Some(&Token::LeftParenthesis) {
+            self.match_word("cube")?;
+            return Ok(GroupingElement::Cube(self.match_grouping_elements()?));
+        }
+        if self.peek_word(0, "grouping") && self.peek_word(1, "sets") {
+            self.match_word("grouping")?;
+            self.match_word("sets")?;
+            return Ok(GroupingElement::GroupingSets(
+                self.match_grouping_elements()?,
+            ));
+        }
+
+        if self.next_token()? == Token::LeftParenthesis && !self.peek_subquery() {
+            let start_index = self.token_index;
+            self.match_token(Token::LeftParenthesis)?;
+            let mut terms: Vec<Term> = Vec::new();
+            // () is the empty grouping set
+            if self.next_token()? != Token::RightParenthesis {
+                loop {
+                    terms.push(self.match_expression()?);
+                    if self.next_token()? != Token::Comma {
+                        break;
+                    }
+                    self.match_token(Token::Comma)?;
+                }
+            }
+            self.match_token(Token::RightParenthesis)?;
+
+            let ends_element = matches!(self.next_token()?, Token::Comma | Token::RightParenthesis);
+            if terms.len() != 1 || (nested && ends_element) {
+                return Ok(GroupingElement::Set(terms));
+            }
+            // a single parenthesised expression like (a + b) * 2 is parsed
+            // again from the start as a regular expression
+            self.token_index = start_index;
+        }
+
+        Ok(GroupingElement::Expression(self.match_expression()?))
+    }
+
+    // matches a parenthesised list of grouping elements
+    fn match_grouping_elements(&mut self) -> Result<Vec<GroupingElement>> {
+        self.match_token(Token::LeftParenthesis)?;
+        let mut elements: Vec<GroupingElement> = Vec::new();
+        loop {
+            elements.push(self.match_grouping_element(true)?);
+            if self.next_token()? != Token::Comma {
+                break;
+            }
+            self.match_token(Token::Comma)?;
+        }
+        self.match_token(Token::RightParenthesis)?;
+        Ok(elements)
+    }
+
+    fn match_named_windows(&mut self) -> Result<Vec<NamedWindow>> {
+        self.log("match_named_windows()".to_string());
+
+        let mut named_windows: Vec<NamedWindow> = Vec::new();
+        if self.next_token()? != Token::Window {
+            return Ok(named_windows);
+        }
+        self.match_token(Token::Window)?;
+
+        loop {
+            let name = self.match_identifier()?;
+            self.match_token(Token::As)?;
+            let specification = self.match_window_specification()?;
+            named_windows.push(NamedWindow {
+                name,
+                specification,
+            });
+
+            if self.next_token()? != Token::Comma {
+                break;
+            }
+            self.match_token(Token::Comma)?;
+        }
+
+        Ok(named_windows)
+    }
+
+    // matches "([existing_window] [PARTITION BY ...] [ORDER BY ...] [frame])"
+    fn match_window_specification(&mut self) -> Result<WindowSpecification> {
+        self.log("match_window_specification()".to_string());
+
+        self.match_token(Token::LeftParenthesis)?;
+
+        let mut existing_window: Option<String> = None;
+        if let Token::Identifier(_) = self.next_token()? {
+            if !self.peek_word(0, "partition") && !self.peek_frame_units() {
+                existing_window = Some(self.match_identifier()?);
+            }
+        }
+
+        let mut partition_by: Vec<Term> = Vec::new();
+        if self.peek_word(0, "partition") {
+            self.match_word("partition")?;
+            self.match_token(Token::By)?;
+            loop {
+                partition_by.push(self.match_expression()?);
+                if self.next_token()? != Token::Comma {
+                    break;
+                }
+                self.match_token(Token::Comma)?;
+            }
+        }
+
+        let mut order_by: Vec<OrderByExpr> = Vec::new();
+        if self.next_token()? == Token::Order {
+            order_by = self.match_order_by()?;
+        }
+
+        let mut frame: Option<WindowFrame> = None;
+        if self.peek_frame_units() {
+            frame = Some(self.match_window_frame()?);
+        }
+
+        self.match_token(Token::RightParenthesis)?;
+
+        Ok(WindowSpecification {
+            existing_window,
+            partition_by,
+            order_by,
+            frame,
+        })
+    }
+
+    fn peek_frame_units(&self) -> bool {
+        self.peek_word(0, "rows") || self.peek_word(0, "range") || self.peek_word(0, "groups")
+    }
+
+    fn match_window_frame(&mut self) -> Result<WindowFrame> {
+        self.log("match_window_frame()".to_string());
+
+        let units = if self.peek_word(0, "rows") {
+            self.match_word("rows")?;
+            FrameUnits::Rows
+        } else if self.peek_word(0, "range") {
+            self.match_word("range")?;
+            FrameUnits::Range
+        } else {
+            self.match_word("groups")?;
+            FrameUnits::Groups
+        };
+
+        let (start, end) = if self.next_token()? == Token::Between {
+            self.match_token(Token::Between)?;
+            let start = self.match_frame_bound(true)?;
+            self.match_token(Token::And)?;
+            (start, Some(self.match_frame_bound(false)?))
+        } else {
+            (self.match_frame_bound(true)?, None)
+        };
+
+        let mut exclusion: Option<FrameExclusion> = None;
+        if self.peek_word(0, "exclude") {
+            self.match_word("exclude")?;
+            exclusion = Some(if self.peek_word(0, "current") {
+                self.match_word("current")?;
+                self.match_word("row")?;
+                FrameExclusion::CurrentRow
+            } else if self.next_token()? == Token::Group {
+                self.match_token(Token::Group)?;
+                FrameExclusion::Group
+            } else if self.peek_word(0, "ties") {
+                self.match_word("ties")?;
+                FrameExclusion::Ties
+            } else {
+                self.match_word("no")?;
+                self.match_word("others")?;
+                FrameExclusion::NoOthers
+            });
+        }
+
+        Ok(WindowFrame {
+            units,
+            start,
+            end,
+            exclusion,
+        })
+    }
+
+    // a frame can neither start at UNBOUNDED FOLLOWING nor end at
+    // UNBOUNDED PRECEDING
+    fn match_frame_bound(&mut self, is_start: bool) -> Result<FrameBound> {
+        if self.peek_word(0, "unbounded") {
+            self.match_word("unbounded")?;
+            if self.peek_word(0, "preceding") && is_start {
+                self.match_word("preceding")?;
+                return Ok(FrameBound::UnboundedPreceding);
+            }
+            if self.peek_word(0, "following") && !is_start {
+                self.match_word("following")?;
+                return Ok(FrameBound::UnboundedFollowing);
+            }
+            return Err(ParseError::InvalidToken(self.next_token()?).into());
+        }
+        if self.peek_word(0, "current") {
+            self.match_word("current")?;
+            self.match_word("row")?;
+            return Ok(FrameBound::CurrentRow);
+        }
+
+        let offset = Box::new(self.match_expression()?);
+        if self.peek_word(0, "preceding") {
+            self.match_word("preceding")?;
+            Ok(FrameBound::Preceding(offset))
+        } else {
+            self.match_word("following")?;
+            Ok(FrameBound::Following(offset))
+        }
+    }
+
+    fn is_grouping_function(name: &ObjectName) -> bool {
+        matches!(name.0.as_slice(), [name] if name.eq_ignore_ascii_case("grouping"))
+    }
+
+    // returns the lowercase name of a built-in aggregate function, schema
+    // qualified names always refer to user-defined functions
+    fn aggregate_function_name(name: &ObjectName) -> Option<String> {
+        let [name] = name.0.as_slice() else {
+            return None;
+        };
+        let name = name.to_lowercase();
+        let is_aggregate = matches!(
+            name.as_str(),
+            "count"
+                | "sum"
+                | "avg"
+                | "min"
+                | "max"
+                | "string_agg"
+                | "array_agg"
+                | "percentile_cont"
+                | "percentile_disc"
+        );
+        is_aggregate.then_some(name)
+    }
+
+    // matches the arguments and trailing clauses of an aggregate function call
+    // after the opening parenthesis
+    fn match_aggregate(&mut self, name: String) -> Result<Function> {
+        self.log("match_aggregate()".to_string());
+
+        let mut distinct = false;
+        if self.next_token()? == Token::Distinct {
+            self.match_token(Token::Distinct)?;
+            distinct = true;
+        } else if self.next_token()? == Token::All {
+            self.match_token(Token::All)?;
+        }
+
+        let mut count_star = false;
+        let mut arguments: Vec<Term> = Vec::new();
+        if name == "count" && self.next_token()? == Token::Star {
+            // count(distinct *) has no meaning
+            if distinct {
+                return Err(ParseError::InvalidToken(Token::Star).into());
+            }
+            self.match_token(Token::Star)?;
+            count_star = true;
+        } else if self.next_token()? != Token::RightParenthesis
+            && self.next_token()? != Token::Order
+        {
+            loop {
+                arguments.push(self.match_expression()?);
+                if self.next_token()? != Token::Comma {
+                    break;
+                }
+                self.match_token(Token::Comma)?;
+            }
+        }
+
+        let mut order_by: Vec<OrderByExpr> = Vec::new();
+        if self.next_token()? == Token::Order {
+            order_by = self.match_order_by()?;
+        }
+        self.match_token(Token::RightParenthesis)?;
+
+        // WITHIN GROUP is required by the ordered set aggregates and is not
+        // allowed on any other aggregate
+        let mut within_group: Vec<OrderByExpr> = Vec::new();
+        let ordered_set = name == "percentile_cont" || name == "percentile_disc";
+        let has_within_group =
+            self.peek_word(0, "within") && self.peek_nth_token(1) == Some(&Token::Group);
+        if ordered_set {
+            self.match_word("within")?;
+            self.match_token(Token::Group)?;
+            self.match_token(Token::LeftParenthesis)?;
+            within_group = self.match_order_by()?;
+            self.match_token(Token::RightParenthesis)?;
+        } else if has_within_group {
+            return Err(ParseError::InvalidToken(self.next_token()?).into());
+        }
+
+        let mut filter: Option<Box<Term>> = None;
+        if self.peek_word(0, "filter") && self.peek_nth_token(1) == Some(&Token::LeftParenthesis) {
+            self.match_word("filter")?;
+            self.match_token(Token::LeftParenthesis)?;
+            self.match_token(Token::Where)?;
+            filter = Some(Box::new(self.match_expression()?));
+            self.match_token(Token::RightParenthesis)?;
+        }
+
+        let function = if count_star {
+            AggregateFunction::Count(CountFunction::Star)
+        } else {
+            let number_of_arguments = arguments.len();
+            let expected_arguments = if name == "string_agg" { 2 } else { 1 };
+            if number_of_arguments != expected_arguments {
+                return Err(ParseError::InvalidArgumentCount(name, number_of_arguments).into());
+            }
+
+            let mut arguments = arguments.into_iter().map(Box::new);
+            let argument = arguments.next().ok_or(ParseError::NoMoreTokens)?;
+            match name.as_str() {
+                "count" => AggregateFunction::Count(CountFunction::Term(argument)),
+                "sum" => AggregateFunction::Sum(argument),
+                "avg" => AggregateFunction::Avg(argument),
+                "min" => AggregateFunction::Min(argument),
+                "max" => AggregateFunction::Max(argument),
+                "string_agg" => AggregateFunction::StringAgg {
+                    expression: argument,
+                    delimiter: arguments.next().ok_or(ParseError::NoMoreTokens)?,
+                },
+                "array_agg" => AggregateFunction::ArrayAgg(argument),
+                "percentile_cont" => AggregateFunction::PercentileCont(argument),
+                "percentile_disc" => AggregateFunction::PercentileDisc(argument),
+                _ => return Err(ParseError::NotImplemented(format!("aggregate {}", name)).into()),
+            }
+        };
+
+        Ok(Function::Aggregate {
+            function,
+            distinct,
+            order_by,
+            within_group,
+            filter,
+        })
+    }
+
+    // matches "ORDER BY expr [ASC | DESC] [NULLS FIRST | LAST], ..."
+    fn match_order_by(&mut self) -> Result<Vec<OrderByExpr>> {
+        self.log("match_order_by()".to_string());
+
+        self.match_token(Token::Order)?;
+        self.match_token(Token::By)?;
+
+        let mut order_by_expressions: Vec<OrderByExpr> = Vec::new();
+        loop {
+            order_by_expressions.push(self.match_order_by_expression()?);
+            if self.next_token()? != Token::Comma {
+                break;
+            }
+            self.match_token(Token::Comma)?;
+        }
+
+        Ok(order_by_expressions)
+    }
+
+    // matches "expression [ASC | DESC] [NULLS {FIRST | LAST}]"
+    fn match_order_by_expression(&mut self) -> Result<OrderByExpr> {
+        let expression = self.match_expression()?;
+
+        let direction = match self.next_token()? {
+            Token::Asc => {
+                self.match_token(Token::Asc)?;
+                Some(OrderDirection::Asc)
+            }
+            Token::Desc => {
+                self.match_token(Token::Desc)?;
+                Some(OrderDirection::Desc)
+            }
+            _ => None,
+        };
+
+        let mut nulls = None;
+        if self.peek_word(0, "nulls") {
+            self.match_word("nulls")?;
+            if self.peek_word(0, "first") {
+                self.match_word("first")?;
+                nulls = Some(NullsOrder::First);
+            } else {
+                self.match_word("last")?;
+                nulls = Some(NullsOrder::Last);
+            }
+        }
+
+        Ok(OrderByExpr {
+            expression,
+            direction,
+            nulls,
+        })
+    }
+
+    fn peek_subquery(&mut self) -> bool {
+        self.peek_match_token_types(vec![Token::LeftParenthesis, Token::Select])
+            || self.peek_match_token_types(vec![Token::LeftParenthesis, Token::With])
+            || self.peek_match_token_types(vec![Token::LeftParenthesis, Token::Values])
+    }
+
+    // matches a parenthesised query
+    fn match_subquery(&mut self) -> Result<Query> {
+        self.log("match_subquery()".to_string());
+
+        self.match_token(Token::LeftParenthesis)?;
+        let query = self.match_query()?;
+        self.match_token(Token::RightParenthesis)?;
+        Ok(query)
+    }
+
+    // matches the "(x AS type)" part of CAST, TRY_CAST and SAFE_CAST
+    fn match_cast(&mut self, kind: CastKind) -> Result<Term> {
+        self.log("match_cast()".to_string());
+
+        self.match_token(Token::LeftParenthesis)?;
+        let expression = self.match_expression()?;
+        self.match_token(Token::As)?;
+        let data_type = self.match_data_type()?;
+        self.match_token(Token::RightParenthesis)?;
+
+        Ok(Term::Cast {
+            kind,
+            expression: Box::new(expression),
+            data_type,
+        })
+    }
+
+    fn match_data_type(&mut self) -> Result<DataType> {
+        self.log("match_data_type()".to_string());
+
+        let first_name = self.match_identifier()?;
+        let mut data_type = match first_name.to_lowercase().as_str() {
+            "tinyint" => DataType::TinyInt,
+            "smallint" | "int2" => DataType::SmallInt,
+            "int" | "integer" | "int4" => DataType::Integer,
+            "bigint" | "int8" => DataType::BigInt,
+            "decimal" | "dec" | "numeric" => {
+                let mut precision = None;
+                let mut scale = None;
+                if self.next_token()? == Token::LeftParenthesis {
+                    self.match_token(Token::LeftParenthesis)?;
+                    precision = Some(self.match_unsigned_integer()?);
+                    if self.next_token()? == Token::Comma {
+                        self.match_token(Token::Comma)?;
+                        scale = Some(self.match_unsigned_integer()?);
+                    }
+                    self.match_token(Token::RightParenthesis)?;
+                }
+                DataType::Decimal { precision, scale }
+            }
+            "real" | "float4" => DataType::Real,
+            "float8" => DataType::Double,
+            "double" => {
+                if self.peek_word(0, "precision") {
+                    self.match_word("precision")?;
+                }
+                DataType::Double
+            }
+            "float" => DataType::Float(self.match_type_length()?),
+            "char" | "character" => {
+                if self.peek_word(0, "varying") {
+                    self.match_word("varying")?;
+                    DataType::Varchar(self.match_type_length()?)
+                } else {
+                    DataType::Char(self.match_type_length()?)
+                }
+            }
+            "varchar" => DataType::Varchar(self.match_type_length()?),
+            "text" => DataType::Text,
+            "boolean" | "bool" => DataType::Boolean,
+            "date" => DataType::Date,
+            "time" => {
+                let precision = self.match_type_length()?;
+                let with_time_zone = self.match_time_zone()?;
+                DataType::Time {
+                    precision,
+                    with_time_zone,
+                }
+            }
+            "timetz" => DataType::Time {
+                precision: self.match_type_length()?,
+                with_time_zone: true,
+            },
+            "timestamp" => {
+                let precision = self.match_type_length()?;
+                let with_time_zone = self.match_time_zone()?;
+                DataType::Timestamp {
+                    precision,
+                    with_time_zone,
+                }
+            }
+            "timestamptz" => DataType::Timestamp {
+                precision: self.match_type_length()?,
+                with_time_zone: true,
+            },
+            "interval" => DataType::Interval,
+            "json" => DataType::Json,
+            "jsonb" => DataType::Jsonb,
+            "uuid" => DataType::Uuid,
+            "bytea" => DataType::Bytea,
+            "blob" => DataType::Blob,
+            _ => {
+                let mut parts = vec![first_name];
+                while self.next_token()? == Token::Period {
+                    self.match_token(Token::Period)?;
+                    parts.push(self.match_identifier()?);
+                }
+
+                let mut modifiers: Vec<String> = Vec::new();
+                if self.next_token()? == Token::LeftParenthesis {
+                    self.match_token(Token::LeftParenthesis)?;
+                    loop {
+                        match self.next_token()? {
+                            Token::Identifier(value)
+                            | Token::Number(value)
+                            | Token::StringToken(value) => modifiers.push(value),
+                            ut => return Err(ParseError::InvalidToken(ut).into()),
+                        }
+                        self.read_next_token();
+                        if self.next_token()? != Token::Comma {
+                            break;
+                        }
+                        self.match_token(Token::Comma)?;
+                    }
+                    self.match_token(Token::RightParenthesis)?;
+                }
+                DataType::Custom {
+                    name: ObjectName(parts),
+                    modifiers,
+                }
+            }
+        };
+
+        // any number of array suffixes, e.g. int[][] or int[3]
+        while self.next_token()? == Token::LeftBracket {
+            self.match_token(Token::LeftBracket)?;
+            let mut size = None;
+            if self.next_token()? != Token::RightBracket {
+                size = Some(self.match_unsigned_integer()?);
+            }
+            self.match_token(Token::RightBracket)?;
+            data_type = DataType::Array {
+                element_type: Box::new(data_type),
+                size,
+            };
+        }
+
+        Ok(data_type)
+    }
+
+    // matches an optional "(n)" length or precision after a type name
+    fn match_type_length(&mut self) -> Result<Option<u64>> {
+        if self.next_token()? != Token::LeftParenthesis {
+            return Ok(None);
+        }
+        self.match_token(Token::LeftParenthesis)?;
+        let length = self.match_unsigned_integer()?;
+        self.match_token(Token::RightParenthesis)?;
+        Ok(Some(length))
+    }
+
+    // matches an optional "WITH TIME ZONE" or "WITHOUT TIME ZONE"
+    fn match_time_zone(&mut self) -> Result<bool> {
+        if self.next_token()? == Token::With && self.peek_word(1, "time") {
+            self.match_token(Token::With)?;
+        } else if self.peek_word(0, "without") {
+            self.match_word("without")?;
+            self.match_word("time")?;
+            self.match_word("zone")?;
+            return Ok(false);
+        } else {
+            return Ok(false);
+        }
+        self.match_word("time")?;
+        self.match_word("zone")?;
+        Ok(true)
+    }
+
+    fn expression_continues(&mut self, last_was_term: bool, operators: &[Token]) -> Result<bool> {
+        let next_token = self.next_token()?;
+        if last_was_term {
+            // a right parenthesis only belongs to this expression when it
+            // closes a parenthesis opened inside of it, and a NOT following a
+            // term starts the next clause, e.g. DEFAULT 0 NOT NULL
+            Ok(
+                (next_token.is_expression_operator() && next_token != Token::Not)
+                    || next_token == Token::DoubleColon
+                    || (next_token == Token::RightParenthesis
+                        && operators.contains(&Token::LeftParenthesis)),
+            )
+        } else {
+            Ok(matches!(
+                next_token,
+                Token::LeftParenthesis
+                    | Token::Identifier(_)
+                    | Token::Number(_)
+                    | Token::StringToken(_)
+                    | Token::True
+                    | Token::False
+                    | Token::Null
+                    | Token::Cast
+                    | Token::Exists
+                    | Token::All
+                    | Token::Any
+                    | Token::Some
+                    | Token::Array
+                    | Token::QuestionMark
+                    | Token::Dollar
+                    | Token::Colon
+            ) || self.peek_match_token_types(vec![Token::Left, Token::LeftParenthesis])
+                || self.peek_match_token_types(vec![Token::Right, Token::LeftParenthesis])
+                || self.peek_match_token_types(vec![Token::Values, Token::LeftParenthesis])
+                || self.peek_match_token_types(vec![Token::Not, Token::Exists]))
+        }
     }
 }
@@ -0,0 +1,3154 @@
+use crate::ast::ast::{
+    AggregateFunction, AlterColumnOperation, AlterTableOperation, Assignment, AssignmentTarget,
+    BindParameter, CastKind, Column, ColumnConstraint, ColumnDefinition, ColumnOption,
+    ConflictAction, ConflictTarget, CountFunction, CteCycle, CteSearch, DataType, DropBehavior,
+    DropName, DropStatement, ForeignKeyReference, FrameBound, FrameExclusion, FrameUnits, Function,
+    GroupBy, GroupingElement, InsertSource, IsolationLevel, JoinConstraint, JoinType, MergeAction,
+    MergeClause, MergeClauseKind, NamedWindow, NullsOrder, Numeric, ObjectName, ObjectType,
+    OnConflict, Operand, OrderByExpr, OrderDirection, Quantifier, Query, ReferentialAction,
+    RowLimit, SearchOrder, SelectExpression, SelectStatement, SetExpression, SetQuantifier,
+    Statement, TableAlias, TableConstraint, TableConstraintKind, TableExpression, Term,
+    TransactionAccessMode, TransactionMode, TruncateStatement, Value, Window, WindowFrame,
+    WindowSpecification,
+};
+
+use super::parser::{ParseError, Parser};
+
+fn parse_statement(query: &str) -> Statement {
+    let mut parser = Parser::new(query.to_string(), false);
+    match parser.parse() {
+        Ok(statement) => statement,
+        Err(err) => panic!("failed to parse {:?}: {:?}", query, err),
+    }
+}
+
+// parses a query and returns the statement inside of the given Statement
+// variant, e.g. parse_as!(query, Statement::Insert)
+macro_rules! parse_as {
+    ($query:expr, $variant:path) => {
+        match parse_statement($query) {
+            $variant(statement) => statement,
+            statement => panic!(
+                "expected {} but found {:?}",
+                stringify!($variant),
+                statement
+            ),
+        }
+    };
+}
+
+// asserts that none of the queries can be parsed
+fn assert_parse_fails(queries: &[&str]) {
+    for query in queries {
+        println!("running test case: {}", query);
+        let mut parser = Parser::new(query.to_string(), false);
+        assert!(parser.parse().is_err(), "expected {:?} to fail", query);
+    }
+}
+
+fn parse_select(query: &str) -> SelectStatement {
+    query_select(parse_as!(query, Statement::Query))
+}
+
+// returns the select of a query that is not a compound query
+fn query_select(query: Query) -> SelectStatement {
+    match query.body {
+        SetExpression::Select(select_statement) => *select_statement,
+        body => panic!("expected a select but found {:?}", body),
+    }
+}
+
+// returns the expression of the first item in the select list with the
+// Term::Operand(Operand::Term(..)) wrapper of a single term removed
+fn first_select_term(query: &str) -> Term {
+    let select_statement = parse_select(query);
+    match select_statement.select_expressions.into_iter().next() {
+        Some(SelectExpression::Expression { expression, .. }) => unwrap_term(expression),
+        other => panic!("expected an expression but found {:?}", other),
+    }
+}
+
+fn unwrap_term(term: Term) -> Term {
+    match term {
+        Term::Operand(operand) => match *operand {
+            Operand::Term(term) => term,
+            operand => Term::Operand(Box::new(operand)),
+        },
+        term => term,
+    }
+}
+
+fn where_operand(query: &str) -> Operand {
+    match parse_select(query).where_expression {
+        Some(Term::Operand(operand)) => *operand,
+        other => panic!("expected a where expression but found {:?}", other),
+    }
+}
+
+fn int(value: i64) -> Term {
+    Term::Value(Value::Numeric(Numeric::Int(value)))
+}
+
+// wraps a term the way match_expression returns a single term expression
+fn expression(term: Term) -> Term {
+    Term::Operand(Box::new(Operand::Term(term)))
+}
+
+fn object_name(parts: &[&str]) -> ObjectName {
+    ObjectName(parts.iter().map(|part| part.to_string()).collect())
+}
+
+fn table(name: &str) -> TableExpression {
+    TableExpression::Table {
+        name: object_name(&[name]),
+        alias: None,
+    }
+}
+
+fn column(name: &str) -> Term {
+    Term::Column(Column::Direct {
+        name: object_name(&[name]),
+    })
+}
+
+#[test]
+fn test_parse_invalid_queries() {
+    assert_parse_fails(&[
+        "",
+        "select a, from bike;",
+        "select a + from bike;",
+        "select cast(a int) from bike;",
+        "select (a + 1 from bike;",
+        "select a::varchar( from bike;",
+        "select cast(a as geometry(point,)) from bike;",
+        "select array[1,] from bike;",
+    ]);
+}
+
+#[test]
+fn test_parse_cast_data_types() {
+    struct TestCase {
+        data_type: String,
+        expected_data_type: DataType,
+    }
+
+    let test_cases = vec![
+        TestCase {
+            data_type: String::from("int"),
+            expected_data_type: DataType::Integer,
+        },
+        TestCase {
+            data_type: String::from("BIGINT"),
+            expected_data_type: DataType::BigInt,
+        },
+        TestCase {
+            data_type: String::from("int2"),
+            expected_data_type: DataType::SmallInt,
+        },
+        TestCase {
+            data_type: String::from("decimal(10, 2)"),
+            expected_data_type: DataType::Decimal {
+                precision: Some(10),
+                scale: Some(2),
+            },
+        },
+        TestCase {
+            data_type: String::from("numeric"),
+            expected_data_type: DataType::Decimal {
+                precision: None,
+                scale: None,
+            },
+        },
+        TestCase {
+            data_type: String::from("double precision"),
+            expected_data_type: DataType::Double,
+        },
+        TestCase {
+            data_type: String::from("varchar(255)"),
+            expected_data_type: DataType::Varchar(Some(255)),
+        },
+        TestCase {
+            data_type: String::from("character varying"),
+            expected_data_type: DataType::Varchar(None),
+        },
+        TestCase {
+            data_type: String::from("text"),
+            expected_data_type: DataType::Text,
+        },
+        TestCase {
+            data_type: String::from("boolean"),
+            expected_data_type: DataType::Boolean,
+        },
+        TestCase {
+            data_type: String::from("timestamp(3) with time zone"),
+            expected_data_type: DataType::Timestamp {
+                precision: Some(3),
+                with_time_zone: true,
+            },
+        },
+        TestCase {
+            data_type: String::from("time without time zone"),
+            expected_data_type: DataType::Time {
+                precision: None,
+                with_time_zone: false,
+            },
+        },
+        TestCase {
+            data_type: String::from("timestamptz"),
+            expected_data_type: DataType::Timestamp {
+                precision: None,
+                with_time_zone: true,
+            },
+        },
+        TestCase {
+            data_type: String::from("jsonb"),
+            expected_data_type: DataType::Jsonb,
+        },
+        TestCase {
+            data_type: String::from("uuid"),
+            expected_data_type: DataType::Uuid,
+        },
+        TestCase {
+            data_type: String::from("bytea"),
+            expected_data_type: DataType::Bytea,
+        },
+        TestCase {
+            data_type: String::from("int[]"),
+            expected_data_type: DataType::Array {
+                element_type: Box::new(DataType::Integer),
+                size: None,
+            },
+        },
+        TestCase {
+            data_type: String::from("text[3][]"),
+            expected_data_type: DataType::Array {
+                element_type: Box::new(DataType::Array {
+                    element_type: Box::new(DataType::Text),
+                    size: Some(3),
+                }),
+                size: None,
+            },
+        },
+        TestCase {
+            data_type: String::from("geometry(point, 4326)"),
+            expected_data_type: DataType::Custom {
+                name: object_name(&["geometry"]),
+                modifiers: vec!["point".to_string(), "4326".to_string()],
+            },
+        },
+    ];
+
+    for test_case in test_cases {
+        println!("running test case: {}", test_case.data_type);
+        let query = format!("select cast(a as {}) from bike;", test_case.data_type);
+        match first_select_term(&query) {
+            Term::Cast {
+                kind, data_type, ..
+            } => {
+                assert_eq!(CastKind::Cast, kind);
+                assert_eq!(test_case.expected_data_type, data_type);
+            }
+            term => panic!("expected a cast but found {:?}", term),
+        }
+    }
+}
+
+#[test]
+fn test_parse_cast_kinds() {
+    struct TestCase {
+        query: String,
+        expected_kind: CastKind,
+    }
+
+    let test_cases = vec![
+        TestCase {
+            query: String::from("select CAST(a AS int) from bike;"),
+            expected_kind: CastKind::Cast,
+        },
+        TestCase {
+            query: String::from("select try_cast(a as int) from bike;"),
+            expected_kind: CastKind::TryCast,
+        },
+        TestCase {
+            query: String::from("select SAFE_CAST(a AS int) from bike;"),
+            expected_kind: CastKind::SafeCast,
+        },
+        TestCase {
+            query: String::from("select a::int from bike;"),
+            expected_kind: CastKind::DoubleColon,
+        },
+    ];
+
+    for test_case in test_cases {
+        println!("running test case: {}", test_case.query);
+        match first_select_term(&test_case.query) {
+            Term::Cast {
+                kind,
+                expression,
+                data_type,
+            } => {
+                assert_eq!(test_case.expected_kind, kind);
+                assert_eq!(column("a"), unwrap_term(*expression));
+                assert_eq!(DataType::Integer, data_type);
+            }
+            term => panic!("expected a cast but found {:?}", term),
+        }
+    }
+}
+
+#[test]
+fn test_parse_double_colon_cast_precedence() {
+    // the cast only applies to the term directly before it
+    match first_select_term("select a + b::int from bike;") {
+        Term::Operand(operand) => match *operand {
+            Operand::Addition(_, right) => {
+                assert!(matches!(*right, Operand::Term(Term::Cast { .. })))
+            }
+            operand => panic!("expected an addition but found {:?}", operand),
+        },
+        term => panic!("expected an operand but found {:?}", term),
+    }
+
+    // a parenthesised expression is cast as a whole and casts can be chained
+    match first_select_term("select (a + b)::text::varchar(10) from bike;") {
+        Term::Cast {
+            expression,
+            data_type,
+            ..
+        } => {
+            assert_eq!(DataType::Varchar(Some(10)), data_type);
+            match *expression {
+                Term::Cast { expression, .. } => {
+                    assert!(matches!(*expression, Term::Operand(_)))
+                }
+                term => panic!("expected a cast but found {:?}", term),
+            }
+        }
+        term => panic!("expected a cast but found {:?}", term),
+    }
+}
+
+#[test]
+fn test_parse_left_associative_operators() {
+    // 1 - 2 + 3 is (1 - 2) + 3
+    match first_select_term("select 1 - 2 + 3 from bike;") {
+        Term::Operand(operand) => match *operand {
+            Operand::Addition(left, _) => {
+                assert!(matches!(*left, Operand::Subtraction(_, _)))
+            }
+            operand => panic!("expected an addition but found {:?}", operand),
+        },
+        term => panic!("expected an operand but found {:?}", term),
+    }
+}
+
+#[test]
+fn test_parse_exists_subqueries() {
+    struct TestCase {
+        query: String,
+        expected_negated: bool,
+    }
+
+    let test_cases = vec![
+        TestCase {
+            query: String::from(
+                "select * from bike where exists (select * from rental where rental.bike_id = id);",
+            ),
+            expected_negated: false,
+        },
+        TestCase {
+            query: String::from(
+                "select * from bike where not exists (select * from rental where rental.bike_id = id);",
+            ),
+            expected_negated: true,
+        },
+    ];
+
+    for test_case in test_cases {
+        println!("running test case: {}", test_case.query);
+        match where_operand(&test_case.query) {
+            Operand::Term(Term::Exists { negated, subquery }) => {
+                assert_eq!(test_case.expected_negated, negated);
+                assert!(query_select(*subquery).where_expression.is_some());
+            }
+            operand => panic!("expected exists but found {:?}", operand),
+        }
+    }
+}
+
+#[test]
+fn test_parse_scalar_subqueries() {
+    match first_select_term("select (select max(price) from bike) as max_price from store;") {
+        Term::Subquery(subquery) => {
+            assert_eq!(1, query_select(*subquery).select_expressions.len())
+        }
+        term => panic!("expected a subquery but found {:?}", term),
+    }
+
+    match where_operand("select * from bike where price > (select avg(price) from bike) + 1;") {
+        Operand::GreaterThan(_, right) => match *right {
+            Operand::Addition(left, _) => {
+                assert!(matches!(*left, Operand::Term(Term::Subquery(_))))
+            }
+            operand => panic!("expected an addition but found {:?}", operand),
+        },
+        operand => panic!("expected a comparison but found {:?}", operand),
+    }
+}
+
+#[test]
+fn test_parse_quantified_comparisons() {
+    match where_operand("select * from bike where price > all (select price from rental);") {
+        Operand::GreaterThan(_, right) => match *right {
+            Operand::Term(Term::Quantified {
+                quantifier,
+                expression,
+            }) => {
+                assert_eq!(Quantifier::All, quantifier);
+                assert!(matches!(*expression, Term::Subquery(_)));
+            }
+            operand => panic!("expected a quantified term but found {:?}", operand),
+        },
+        operand => panic!("expected a comparison but found {:?}", operand),
+    }
+
+    match where_operand("select * from bike where id = any (array[1, 2]);") {
+        Operand::Equal(_, right) => match *right {
+            Operand::Term(Term::Quantified {
+                quantifier,
+                expression,
+            }) => {
+                assert_eq!(Quantifier::Any, quantifier);
+                match unwrap_term(*expression) {
+                    Term::Array(elements) => assert_eq!(
+                        vec![int(1), int(2)],
+                        elements.into_iter().map(unwrap_term).collect::<Vec<Term>>()
+                    ),
+                    term => panic!("expected an array but found {:?}", term),
+                }
+            }
+            operand => panic!("expected a quantified term but found {:?}", operand),
+        },
+        operand => panic!("expected a comparison but found {:?}", operand),
+    }
+
+    match where_operand("select * from bike where id <= some (select id from rental);") {
+        Operand::LessThanOrEqual(_, right) => assert!(matches!(
+            *right,
+            Operand::Term(Term::Quantified {
+                quantifier: Quantifier::Some,
+                ..
+            })
+        )),
+        operand => panic!("expected a comparison but found {:?}", operand),
+    }
+}
+
+#[test]
+fn test_parse_aggregate_functions() {
+    struct TestCase {
+        query: String,
+        expected_function: Function,
+    }
+
+    let test_cases = vec![
+        TestCase {
+            query: String::from("select count(*) from bike;"),
+            expected_function: Function::Aggregate {
+                function: AggregateFunction::Count(CountFunction::Star),
+                distinct: false,
+                order_by: vec![],
+                within_group: vec![],
+                filter: None,
+            },
+        },
+        TestCase {
+            query: String::from("select COUNT(DISTINCT store) from bike;"),
+            expected_function: Function::Aggregate {
+                function: AggregateFunction::Count(CountFunction::Term(Box::new(expression(
+                    column("store"),
+                )))),
+                distinct: true,
+                order_by: vec![],
+                within_group: vec![],
+                filter: None,
+            },
+        },
+        TestCase {
+            query: String::from("select sum(price) filter (where price > 10) from bike;"),
+            expected_function: Function::Aggregate {
+                function: AggregateFunction::Sum(Box::new(expression(column("price")))),
+                distinct: false,
+                order_by: vec![],
+                within_group: vec![],
+                filter: Some(Box::new(Term::Operand(Box::new(Operand::GreaterThan(
+                    Box::new(Operand::Term(column("price"))),
+                    Box::new(Operand::Term(int(10))),
+                ))))),
+            },
+        },
+        TestCase {
+            query: String::from("select max(price) from bike;"),
+            expected_function: Function::Aggregate {
+                function: AggregateFunction::Max(Box::new(expression(column("price")))),
+                distinct: false,
+                order_by: vec![],
+                within_group: vec![],
+                filter: None,
+            },
+        },
+        TestCase {
+            query: String::from(
+                "select string_agg(name, ',' order by name desc nulls last) from bike;",
+            ),
+            expected_function: Function::Aggregate {
+                function: AggregateFunction::StringAgg {
+                    expression: Box::new(expression(column("name"))),
+                    delimiter: Box::new(expression(Term::Value(Value::String(",".to_string())))),
+                },
+                distinct: false,
+                order_by: vec![OrderByExpr {
+                    expression: expression(column("name")),
+                    direction: Some(OrderDirection::Desc),
+                    nulls: Some(NullsOrder::Last),
+                }],
+                within_group: vec![],
+                filter: None,
+            },
+        },
+        TestCase {
+            query: String::from(
+                "select percentile_cont(0.5) within group (order by price) from bike;",
+            ),
+            expected_function: Function::Aggregate {
+                function: AggregateFunction::PercentileCont(Box::new(expression(Term::Value(
+                    Value::Numeric(Numeric::Float(0.5)),
+                )))),
+                distinct: false,
+                order_by: vec![],
+                within_group: vec![OrderByExpr {
+                    expression: expression(column("price")),
+                    direction: None,
+                    nulls: None,
+                }],
+                filter: None,
+            },
+        },
+        TestCase {
+            query: String::from("select lower(name) from bike;"),
+            expected_function: Function::UserDefined {
+                name: object_name(&["lower"]),
+                terms: vec![expression(column("name"))],
+            },
+        },
+    ];
+
+    for test_case in test_cases {
+        println!("running test case: {}", test_case.query);
+        match first_select_term(&test_case.query) {
+            Term::Function(function) => assert_eq!(test_case.expected_function, function),
+            term => panic!("expected a function but found {:?}", term),
+        }
+    }
+}
+
+#[test]
+fn test_parse_invalid_aggregate_functions() {
+    assert_parse_fails(&[
+        "select sum(*) from bike;",
+        "select sum(a, b) from bike;",
+        "select string_agg(name) from bike;",
+        "select avg() from bike;",
+        "select string_agg(a ',') from bike;",
+        "select sum(a,) from bike;",
+        "select f(a b) from bike;",
+        "select f(a,) from bike;",
+        "select count(distinct *) from bike;",
+        "select count(*) within group (order by price) from bike;",
+        "select sum(price) within group (order by price) from bike;",
+        "select percentile_cont(0.5) from bike;",
+        "select percentile_disc(0.5) filter (where price > 0) from bike;",
+    ]);
+}
+
+#[test]
+fn test_parse_window_functions() {
+    struct TestCase {
+        query: String,
+        expected_window: Window,
+    }
+
+    let test_cases = vec![
+        TestCase {
+            query: String::from("select row_number() over () from bike;"),
+            expected_window: Window::Specification(WindowSpecification {
+                existing_window: None,
+                partition_by: vec![],
+                order_by: vec![],
+                frame: None,
+            }),
+        },
+        TestCase {
+            query: String::from(
+                "select row_number() over (partition by store order by price desc) from bike;",
+            ),
+            expected_window: Window::Specification(WindowSpecification {
+                existing_window: None,
+                partition_by: vec![expression(column("store"))],
+                order_by: vec![OrderByExpr {
+                    expression: expression(column("price")),
+                    direction: Some(OrderDirection::Desc),
+                    nulls: None,
+                }],
+                frame: None,
+            }),
+        },
+        TestCase {
+            query: String::from("select sum(price) over w from bike window w as (order by id);"),
+            expected_window: Window::Named("w".to_string()),
+        },
+        TestCase {
+            query: String::from(
+                "select sum(price) over (order by id rows between unbounded preceding and current row) from bike;",
+            ),
+            expected_window: Window::Specification(WindowSpecification {
+                existing_window: None,
+                partition_by: vec![],
+                order_by: vec![OrderByExpr {
+                    expression: expression(column("id")),
+                    direction: None,
+                    nulls: None,
+                }],
+                frame: Some(WindowFrame {
+                    units: FrameUnits::Rows,
+                    start: FrameBound::UnboundedPreceding,
+                    end: Some(FrameBound::CurrentRow),
+                    exclusion: None,
+                }),
+            }),
+        },
+        TestCase {
+            query: String::from(
+                "select avg(price) over (w range between 2 preceding and unbounded following exclude ties) from bike;",
+            ),
+            expected_window: Window::Specification(WindowSpecification {
+                existing_window: Some("w".to_string()),
+                partition_by: vec![],
+                order_by: vec![],
+                frame: Some(WindowFrame {
+                    units: FrameUnits::Range,
+                    start: FrameBound::Preceding(Box::new(expression(int(2)))),
+                    end: Some(FrameBound::UnboundedFollowing),
+                    exclusion: Some(FrameExclusion::Ties),
+                }),
+            }),
+        },
+        TestCase {
+            query: String::from(
+                "select count(*) over (order by id groups 1 following exclude no others) from bike;",
+            ),
+            expected_window: Window::Specification(WindowSpecification {
+                existing_window: None,
+                partition_by: vec![],
+                order_by: vec![OrderByExpr {
+                    expression: expression(column("id")),
+                    direction: None,
+                    nulls: None,
+                }],
+                frame: Some(WindowFrame {
+                    units: FrameUnits::Groups,
+                    start: FrameBound::Following(Box::new(expression(int(1)))),
+                    end: None,
+                    exclusion: Some(FrameExclusion::NoOthers),
+                }),
+            }),
+        },
+    ];
+
+    for test_case in test_cases {
+        println!("running test case: {}", test_case.query);
+        match first_select_term(&test_case.query) {
+            Term::Function(Function::Window { window, .. }) => {
+                assert_eq!(test_case.expected_window, window)
+            }
+            term => panic!("expected a window function but found {:?}", term),
+        }
+    }
+}
+
+#[test]
+fn test_parse_invalid_window_frames() {
+    assert_parse_fails(&[
+        "select sum(price) over (order by id rows unbounded following) from bike;",
+        "select sum(price) over (order by id rows between current row and unbounded preceding) from bike;",
+        "select sum(price) over (order by id rows between unbounded following and current row) from bike;",
+        "select sum(price) over (order by id rows between unbounded and current row) from bike;",
+    ]);
+}
+
+#[test]
+fn test_parse_named_windows() {
+    let select_statement = parse_select(
+        "select rank() over w1, sum(price) over w2 from bike window w1 as (partition by store), w2 as (w1 order by id);",
+    );
+    assert_eq!(
+        vec![
+            NamedWindow {
+                name: "w1".to_string(),
+                specification: WindowSpecification {
+                    existing_window: None,
+                    partition_by: vec![expression(column("store"))],
+                    order_by: vec![],
+                    frame: None,
+                },
+            },
+            NamedWindow {
+                name: "w2".to_string(),
+                specification: WindowSpecification {
+                    existing_window: Some("w1".to_string()),
+                    partition_by: vec![],
+                    order_by: vec![OrderByExpr {
+                        expression: expression(column("id")),
+                        direction: None,
+                        nulls: None,
+                    }],
+                    frame: None,
+                },
+            },
+        ],
+        select_statement.named_windows
+    );
+}
+
+#[test]
+fn test_parse_qualified_names() {
+    let select_statement = parse_select(
+        "select b.*, store.public.bike.*, b.price, db.store.bike.price, public.discount(b.price) from db.store.bike;",
+    );
+
+    assert_eq!(
+        vec![
+            SelectExpression::Family {
+                name: object_name(&["b"]),
+            },
+            SelectExpression::Family {
+                name: object_name(&["store", "public", "bike"]),
+            },
+            SelectExpression::Expression {
+                expression: expression(Term::Column(Column::Direct {
+                    name: object_name(&["b", "price"]),
+                })),
+                alias: None,
+            },
+            SelectExpression::Expression {
+                expression: expression(Term::Column(Column::Direct {
+                    name: object_name(&["db", "store", "bike", "price"]),
+                })),
+                alias: None,
+            },
+            SelectExpression::Expression {
+                expression: expression(Term::Function(Function::UserDefined {
+                    name: object_name(&["public", "discount"]),
+                    terms: vec![expression(Term::Column(Column::Direct {
+                        name: object_name(&["b", "price"]),
+                    }))],
+                })),
+                alias: None,
+            },
+        ],
+        select_statement.select_expressions
+    );
+    assert_eq!(
+        vec![TableExpression::Table {
+            name: object_name(&["db", "store", "bike"]),
+            alias: None,
+        }],
+        select_statement.from_expressions
+    );
+
+    // a qualified name is never a built-in aggregate
+    match first_select_term("select stats.sum(price) from bike;") {
+        Term::Function(Function::UserDefined { name, .. }) => {
+            assert_eq!(object_name(&["stats", "sum"]), name)
+        }
+        term => panic!("expected a user-defined function but found {:?}", term),
+    }
+
+    match first_select_term("select a::public.mood from bike;") {
+        Term::Cast { data_type, .. } => assert_eq!(
+            DataType::Custom {
+                name: object_name(&["public", "mood"]),
+                modifiers: vec![],
+            },
+            data_type
+        ),
+        term => panic!("expected a cast but found {:?}", term),
+    }
+}
+
+#[test]
+fn test_parse_order_by() {
+    struct TestCase {
+        query: String,
+        expected_order_by: Vec<OrderByExpr>,
+    }
+
+    let test_cases = vec![
+        TestCase {
+            query: String::from("select * from bike;"),
+            expected_order_by: vec![],
+        },
+        TestCase {
+            query: String::from("select * from bike order by price;"),
+            expected_order_by: vec![OrderByExpr {
+                expression: expression(column("price")),
+                direction: None,
+                nulls: None,
+            }],
+        },
+        TestCase {
+            query: String::from(
+                "select price * 2 as doubled from bike where id > 1 order by doubled DESC NULLS FIRST, 1 asc, lower(name) nulls last;",
+            ),
+            expected_order_by: vec![
+                OrderByExpr {
+                    expression: expression(column("doubled")),
+                    direction: Some(OrderDirection::Desc),
+                    nulls: Some(NullsOrder::First),
+                },
+                OrderByExpr {
+                    expression: expression(int(1)),
+                    direction: Some(OrderDirection::Asc),
+                    nulls: None,
+                },
+                OrderByExpr {
+                    expression: expression(Term::Function(Function::UserDefined {
+                        name: object_name(&["lower"]),
+                        terms: vec![expression(column("name"))],
+                    })),
+                    direction: None,
+                    nulls: Some(NullsOrder::Last),
+                },
+            ],
+        },
+        TestCase {
+            query: String::from("select * from bike order by price + 1 desc;"),
+            expected_order_by: vec![OrderByExpr {
+                expression: Term::Operand(Box::new(Operand::Addition(
+                    Box::new(Operand::Term(column("price"))),
+                    Box::new(Operand::Term(int(1))),
+                ))),
+                direction: Some(OrderDirection::Desc),
+                nulls: None,
+            }],
+        },
+    ];
+
+    for test_case in test_cases {
+        println!("running test case: {}", test_case.query);
+        let query = parse_as!(&test_case.query, Statement::Query);
+        assert_eq!(test_case.expected_order_by, query.order_by);
+    }
+}
+
+#[test]
+fn test_parse_row_limits() {
+    struct TestCase {
+        query: String,
+        expected_limit: Option<RowLimit>,
+    }
+
+    let test_cases = vec![
+        TestCase {
+            query: String::from("select * from bike;"),
+            expected_limit: None,
+        },
+        TestCase {
+            query: String::from("select * from bike order by id limit 10;"),
+            expected_limit: Some(RowLimit {
+                count: Some(expression(int(10))),
+                offset: None,
+                percent: false,
+                with_ties: false,
+            }),
+        },
+        TestCase {
+            query: String::from("select * from bike limit 10 offset 20;"),
+            expected_limit: Some(RowLimit {
+                count: Some(expression(int(10))),
+                offset: Some(expression(int(20))),
+                percent: false,
+                with_ties: false,
+            }),
+        },
+        TestCase {
+            query: String::from("select * from bike limit 20, 10;"),
+            expected_limit: Some(RowLimit {
+                count: Some(expression(int(10))),
+                offset: Some(expression(int(20))),
+                percent: false,
+                with_ties: false,
+            }),
+        },
+        TestCase {
+            query: String::from("select * from bike limit all offset ?;"),
+            expected_limit: Some(RowLimit {
+                count: None,
+                offset: Some(expression(Term::BindParameter(BindParameter::Anonymous))),
+                percent: false,
+                with_ties: false,
+            }),
+        },
+        TestCase {
+            query: String::from(
+                "select * from bike order by price offset 5 rows fetch first 10 rows only;",
+            ),
+            expected_limit: Some(RowLimit {
+                count: Some(expression(int(10))),
+                offset: Some(expression(int(5))),
+                percent: false,
+                with_ties: false,
+            }),
+        },
+        TestCase {
+            query: String::from("select * from bike order by price fetch next row with ties;"),
+            expected_limit: Some(RowLimit {
+                count: Some(expression(int(1))),
+                offset: None,
+                percent: false,
+                with_ties: true,
+            }),
+        },
+        TestCase {
+            query: String::from("select * from bike limit $1 offset :start;"),
+            expected_limit: Some(RowLimit {
+                count: Some(expression(Term::BindParameter(BindParameter::Positional(
+                    1,
+                )))),
+                offset: Some(expression(Term::BindParameter(BindParameter::Named(
+                    "start".to_string(),
+                )))),
+                percent: false,
+                with_ties: false,
+            }),
+        },
+    ];
+
+    for test_case in test_cases {
+        println!("running test case: {}", test_case.query);
+        let query = parse_as!(&test_case.query, Statement::Query);
+        assert_eq!(test_case.expected_limit, query.limit);
+    }
+}
+
+#[test]
+fn test_parse_top() {
+    struct TestCase {
+        query: String,
+        expected_limit: Option<RowLimit>,
+    }
+
+    let test_cases = vec![
+        TestCase {
+            query: String::from("select top (5) percent with ties * from bike order by price;"),
+            expected_limit: Some(RowLimit {
+                count: Some(expression(int(5))),
+                offset: None,
+                percent: true,
+                with_ties: true,
+            }),
+        },
+        TestCase {
+            query: String::from("select TOP 3 id from bike;"),
+            expected_limit: Some(RowLimit {
+                count: Some(expression(int(3))),
+                offset: None,
+                percent: false,
+                with_ties: false,
+            }),
+        },
+    ];
+
+    for test_case in test_cases {
+        println!("running test case: {}", test_case.query);
+        let query = parse_as!(&test_case.query, Statement::Query);
+        assert_eq!(None, query.limit);
+        assert_eq!(test_case.expected_limit, query_select(query).top);
+    }
+}
+
+#[test]
+fn test_parse_invalid_row_limits() {
+    assert_parse_fails(&[
+        "select * from bike limit;",
+        "select * from bike fetch first 10 rows;",
+        "select top (1) * from bike limit 1;",
+    ]);
+}
+
+#[test]
+fn test_parse_group_by() {
+    struct TestCase {
+        query: String,
+        expected_group_by: Option<GroupBy>,
+    }
+
+    let test_cases = vec![
+        TestCase {
+            query: String::from("select store from bike;"),
+            expected_group_by: None,
+        },
+        TestCase {
+            query: String::from(
+                "select store, count(*) from bike group by store, (price + 1) * 2;",
+            ),
+            expected_group_by: Some(GroupBy::Elements(vec![
+                GroupingElement::Expression(expression(column("store"))),
+                GroupingElement::Expression(Term::Operand(Box::new(Operand::Multiplication(
+                    Box::new(Operand::Addition(
+                        Box::new(Operand::Term(column("price"))),
+                        Box::new(Operand::Term(int(1))),
+                    )),
+                    Box::new(Operand::Term(int(2))),
+                )))),
+            ])),
+        },
+        TestCase {
+            query: String::from("select store, count(*) from bike group by all;"),
+            expected_group_by: Some(GroupBy::All),
+        },
+        TestCase {
+            query: String::from(
+                "select a, b, count(*) from bike group by grouping sets ((a, b), (a), ());",
+            ),
+            expected_group_by: Some(GroupBy::Elements(vec![GroupingElement::GroupingSets(
+                vec![
+                    GroupingElement::Set(vec![expression(column("a")), expression(column("b"))]),
+                    GroupingElement::Set(vec![expression(column("a"))]),
+                    GroupingElement::Set(vec![]),
+                ],
+            )])),
+        },
+        TestCase {
+            query: String::from(
+                "select a, b, c, count(*) from bike group by a, rollup ((b, c), a), cube (b, c);",
+            ),
+            expected_group_by: Some(GroupBy::Elements(vec![
+                GroupingElement::Expression(expression(column("a"))),
+                GroupingElement::Rollup(vec![
+                    GroupingElement::Set(vec![expression(column("b")), expression(column("c"))]),
+                    GroupingElement::Expression(expression(column("a"))),
+                ]),
+                GroupingElement::Cube(vec![
+                    GroupingElement::Expression(expression(column("b"))),
+                    GroupingElement::Expression(expression(column("c"))),
+                ]),
+            ])),
+        },
+    ];
+
+    for test_case in test_cases {
+        println!("running test case: {}", test_case.query);
+        let select_statement = parse_select(&test_case.query);
+        assert_eq!(test_case.expected_group_by, select_statement.group_by);
+    }
+}
+
+#[test]
+fn test_parse_having_and_grouping_function() {
+    let query = parse_as!(
+        "select grouping(store, model), count(*) from bike group by rollup (store, model) having count(*) > 1 order by 1;",
+        Statement::Query
+    );
+    assert_eq!(1, query.order_by.len());
+    let select_statement = query_select(query);
+
+    match select_statement.select_expressions.first() {
+        Some(SelectExpression::Expression {
+            expression: term, ..
+        }) => assert_eq!(
+            expression(Term::Function(Function::Grouping(vec![
+                expression(column("store")),
+                expression(column("model")),
+            ]))),
+            *term
+        ),
+        other => panic!("expected an expression but found {:?}", other),
+    }
+    match select_statement.having {
+        Some(Term::Operand(operand)) => match *operand {
+            Operand::GreaterThan(left, _) => assert!(matches!(
+                *left,
+                Operand::Term(Term::Function(Function::Aggregate { .. }))
+            )),
+            operand => panic!("expected a comparison but found {:?}", operand),
+        },
+        other => panic!("expected a having expression but found {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_invalid_group_by() {
+    assert_parse_fails(&[
+        "select grouping(a,), count(*) from bike group by a;",
+        "select grouping(), count(*) from bike group by a;",
+        "select a, count(*) from bike group by rollup (a,);",
+        "select a, count(*) from bike group by rollup ();",
+        "select a, count(*) from bike group by grouping sets ((a,), ());",
+        "select a, count(*) from bike group by (a b);",
+    ]);
+}
+
+#[test]
+fn test_parse_set_quantifiers() {
+    struct TestCase {
+        query: String,
+        expected_set_quantifier: Option<SetQuantifier>,
+        expected_select_expressions: usize,
+    }
+
+    let test_cases = vec![
+        TestCase {
+            query: String::from("select store from bike;"),
+            expected_set_quantifier: None,
+            expected_select_expressions: 1,
+        },
+        TestCase {
+            query: String::from("select all store from bike;"),
+            expected_set_quantifier: Some(SetQuantifier::All),
+            expected_select_expressions: 1,
+        },
+        TestCase {
+            query: String::from("select DISTINCT store, model from bike;"),
+            expected_set_quantifier: Some(SetQuantifier::Distinct),
+            expected_select_expressions: 2,
+        },
+        TestCase {
+            query: String::from(
+                "select distinct on (store, lower(model)) * from bike order by store;",
+            ),
+            expected_set_quantifier: Some(SetQuantifier::DistinctOn(vec![
+                expression(column("store")),
+                expression(Term::Function(Function::UserDefined {
+                    name: object_name(&["lower"]),
+                    terms: vec![expression(column("model"))],
+                })),
+            ])),
+            expected_select_expressions: 1,
+        },
+        TestCase {
+            query: String::from("select distinct top 5 store from bike;"),
+            expected_set_quantifier: Some(SetQuantifier::Distinct),
+            expected_select_expressions: 1,
+        },
+    ];
+
+    for test_case in test_cases {
+        println!("running test case: {}", test_case.query);
+        let select_statement = parse_select(&test_case.query);
+        assert_eq!(
+            test_case.expected_set_quantifier,
+            select_statement.set_quantifier
+        );
+        assert_eq!(
+            test_case.expected_select_expressions,
+            select_statement.select_expressions.len()
+        );
+    }
+}
+
+fn join(
+    left: TableExpression,
+    right: TableExpression,
+    join_type: JoinType,
+    constraint: JoinConstraint,
+) -> TableExpression {
+    TableExpression::Join {
+        left: Box::new(left),
+        right: Box::new(right),
+        join_type,
+        constraint,
+    }
+}
+
+#[test]
+fn test_parse_joins() {
+    struct TestCase {
+        query: String,
+        expected_from_expressions: Vec<TableExpression>,
+    }
+
+    let qualified_equal = |left: &[&str], right: &[&str]| {
+        JoinConstraint::On(Term::Operand(Box::new(Operand::Equal(
+            Box::new(Operand::Term(Term::Column(Column::Direct {
+                name: object_name(left),
+            }))),
+            Box::new(Operand::Term(Term::Column(Column::Direct {
+                name: object_name(right),
+            }))),
+        ))))
+    };
+
+    let test_cases = vec![
+        TestCase {
+            query: String::from("select * from bike, store;"),
+            expected_from_expressions: vec![table("bike"), table("store")],
+        },
+        TestCase {
+            query: String::from("select * from bike join store on bike.store_id = store.id;"),
+            expected_from_expressions: vec![join(
+                table("bike"),
+                table("store"),
+                JoinType::Inner,
+                qualified_equal(&["bike", "store_id"], &["store", "id"]),
+            )],
+        },
+        TestCase {
+            query: String::from(
+                "select * from bike inner join store using (store_id) left outer join rental using (bike_id, store_id);",
+            ),
+            expected_from_expressions: vec![join(
+                join(
+                    table("bike"),
+                    table("store"),
+                    JoinType::Inner,
+                    JoinConstraint::Using(vec!["store_id".to_string()]),
+                ),
+                table("rental"),
+                JoinType::Left,
+                JoinConstraint::Using(vec!["bike_id".to_string(), "store_id".to_string()]),
+            )],
+        },
+        TestCase {
+            query: String::from(
+                "select * from bike right join store on bike.store_id = store.id full join rental on rental.bike_id = bike.id;",
+            ),
+            expected_from_expressions: vec![join(
+                join(
+                    table("bike"),
+                    table("store"),
+                    JoinType::Right,
+                    qualified_equal(&["bike", "store_id"], &["store", "id"]),
+                ),
+                table("rental"),
+                JoinType::Full,
+                qualified_equal(&["rental", "bike_id"], &["bike", "id"]),
+            )],
+        },
+        TestCase {
+            query: String::from(
+                "select * from bike cross join store natural left join rental, color;",
+            ),
+            expected_from_expressions: vec![
+                join(
+                    join(
+                        table("bike"),
+                        table("store"),
+                        JoinType::Cross,
+                        JoinConstraint::None,
+                    ),
+                    table("rental"),
+                    JoinType::Left,
+                    JoinConstraint::Natural,
+                ),
+                table("color"),
+            ],
+        },
+        TestCase {
+            query: String::from(
+                "select * from bike join (store natural join rental) on bike.store_id = store.id;",
+            ),
+            expected_from_expressions: vec![join(
+                table("bike"),
+                TableExpression::Nested(Box::new(join(
+                    table("store"),
+                    table("rental"),
+                    JoinType::Inner,
+                    JoinConstraint::Natural,
+                ))),
+                JoinType::Inner,
+                qualified_equal(&["bike", "store_id"], &["store", "id"]),
+            )],
+        },
+        TestCase {
+            query: String::from(
+                "select * from bike left join store join rental on rental.store_id = store.id on store.id = bike.store_id;",
+            ),
+            expected_from_expressions: vec![join(
+                table("bike"),
+                join(
+                    table("store"),
+                    table("rental"),
+                    JoinType::Inner,
+                    qualified_equal(&["rental", "store_id"], &["store", "id"]),
+                ),
+                JoinType::Left,
+                qualified_equal(&["store", "id"], &["bike", "store_id"]),
+            )],
+        },
+        TestCase {
+            query: String::from(
+                "select * from bike join store cross join color using (store_id) join rental on true;",
+            ),
+            expected_from_expressions: vec![join(
+                join(
+                    table("bike"),
+                    join(
+                        table("store"),
+                        table("color"),
+                        JoinType::Cross,
+                        JoinConstraint::None,
+                    ),
+                    JoinType::Inner,
+                    JoinConstraint::Using(vec!["store_id".to_string()]),
+                ),
+                table("rental"),
+                JoinType::Inner,
+                JoinConstraint::On(expression(Term::Value(Value::Boolean(true)))),
+            )],
+        },
+    ];
+
+    for test_case in test_cases {
+        println!("running test case: {}", test_case.query);
+        let select_statement = parse_select(&test_case.query);
+        assert_eq!(
+            test_case.expected_from_expressions,
+            select_statement.from_expressions
+        );
+    }
+}
+
+#[test]
+fn test_parse_invalid_joins() {
+    assert_parse_fails(&[
+        "select * from bike join store;",
+        "select * from bike natural cross join store;",
+        "select * from bike left store on a = b;",
+        "select * from bike join store using ();",
+        "select * from (bike join store on a = b;",
+        "select * from bike join store join rental on a = b;",
+    ]);
+}
+
+#[test]
+fn test_parse_left_and_right_functions() {
+    match first_select_term("select left(name, 2) from bike;") {
+        Term::Function(Function::UserDefined { name, terms }) => {
+            assert_eq!(object_name(&["left"]), name);
+            assert_eq!(2, terms.len());
+        }
+        term => panic!("expected a function but found {:?}", term),
+    }
+}
+
+fn table_alias(name: &str, columns: &[&str]) -> Option<TableAlias> {
+    Some(TableAlias {
+        name: name.to_string(),
+        columns: columns.iter().map(|column| column.to_string()).collect(),
+    })
+}
+
+#[test]
+fn test_parse_table_aliases() {
+    struct TestCase {
+        query: String,
+        expected_aliases: Vec<Option<TableAlias>>,
+    }
+
+    let test_cases = vec![
+        TestCase {
+            query: String::from("select * from bike b where b.id = 1;"),
+            expected_aliases: vec![table_alias("b", &[])],
+        },
+        TestCase {
+            query: String::from("select * from bike as b, store s;"),
+            expected_aliases: vec![table_alias("b", &[]), table_alias("s", &[])],
+        },
+        TestCase {
+            query: String::from("select * from bike where id = 1;"),
+            expected_aliases: vec![None],
+        },
+        TestCase {
+            query: String::from("select * from bike as b(id, name) order by id;"),
+            expected_aliases: vec![table_alias("b", &["id", "name"])],
+        },
+        TestCase {
+            query: String::from("select * from (select id, name from bike) as t(a, b);"),
+            expected_aliases: vec![table_alias("t", &["a", "b"])],
+        },
+        TestCase {
+            query: String::from("select * from (select id from bike) t limit 1;"),
+            expected_aliases: vec![table_alias("t", &[])],
+        },
+    ];
+
+    for test_case in test_cases {
+        println!("running test case: {}", test_case.query);
+        let select_statement = parse_select(&test_case.query);
+        let aliases: Vec<Option<TableAlias>> = select_statement
+            .from_expressions
+            .into_iter()
+            .map(|from_expression| match from_expression {
+                TableExpression::Table { alias, .. } => alias,
+                TableExpression::Select { alias, .. } => alias,
+                from_expression => panic!("expected a table but found {:?}", from_expression),
+            })
+            .collect();
+        assert_eq!(test_case.expected_aliases, aliases);
+    }
+
+    // aliases on both sides of a join
+    match parse_select("select * from bike b join store s on b.store_id = s.id;")
+        .from_expressions
+        .first()
+    {
+        Some(TableExpression::Join { left, right, .. }) => {
+            assert!(matches!(
+                **left,
+                TableExpression::Table { alias: Some(_), .. }
+            ));
+            assert!(matches!(
+                **right,
+                TableExpression::Table { alias: Some(_), .. }
+            ));
+        }
+        other => panic!("expected a join but found {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_select_expression_aliases() {
+    let select_statement =
+        parse_select("select price p, price * 2 as doubled, count(*) total, name from bike;");
+    let aliases: Vec<Option<String>> = select_statement
+        .select_expressions
+        .into_iter()
+        .map(|select_expression| match select_expression {
+            SelectExpression::Expression { alias, .. } => alias,
+            other => panic!("expected an expression but found {:?}", other),
+        })
+        .collect();
+    assert_eq!(
+        vec![
+            Some("p".to_string()),
+            Some("doubled".to_string()),
+            Some("total".to_string()),
+            None,
+        ],
+        aliases
+    );
+}
+
+#[test]
+fn test_parse_common_table_expressions() {
+    let query = parse_as!(
+        "with cheap as (select * from bike where price < 100), stores (id, name) as not materialized (select id, name from store) select * from cheap join stores on cheap.store_id = stores.id;",
+        Statement::Query
+    );
+
+    let with = query.with.clone().expect("expected a with clause");
+    assert!(!with.recursive);
+    assert_eq!(2, with.common_table_expressions.len());
+
+    let cheap = &with.common_table_expressions[0];
+    assert_eq!("cheap", cheap.name);
+    assert!(cheap.columns.is_empty());
+    assert_eq!(None, cheap.materialized);
+    assert!(query_select(*cheap.query.clone())
+        .where_expression
+        .is_some());
+
+    let stores = &with.common_table_expressions[1];
+    assert_eq!("stores", stores.name);
+    assert_eq!(vec!["id".to_string(), "name".to_string()], stores.columns);
+    assert_eq!(Some(false), stores.materialized);
+
+    assert_eq!(1, query_select(query).from_expressions.len());
+
+    // a select without a with clause
+    assert_eq!(
+        None,
+        parse_as!("select * from bike;", Statement::Query).with
+    );
+}
+
+#[test]
+fn test_parse_recursive_common_table_expressions() {
+    let query = parse_as!(
+        "with recursive tree (id, parent_id) as materialized (select id, parent_id from node) search depth first by id set ordercol cycle id set is_cycle to true default false using path select * from tree order by ordercol;",
+        Statement::Query
+    );
+
+    let with = query.with.clone().expect("expected a with clause");
+    assert!(with.recursive);
+
+    let tree = &with.common_table_expressions[0];
+    assert_eq!(Some(true), tree.materialized);
+    assert_eq!(
+        Some(CteSearch {
+            order: SearchOrder::DepthFirst,
+            columns: vec!["id".to_string()],
+            sequence_column: "ordercol".to_string(),
+        }),
+        tree.search
+    );
+    assert_eq!(
+        Some(CteCycle {
+            columns: vec!["id".to_string()],
+            mark_column: "is_cycle".to_string(),
+            mark_value: Some(expression(Term::Value(Value::Boolean(true)))),
+            default_value: Some(expression(Term::Value(Value::Boolean(false)))),
+            path_column: "path".to_string(),
+        }),
+        tree.cycle
+    );
+
+    let query = parse_as!(
+        "with recursive t as (select 1 as n from dual) search breadth first by n, m set seq cycle n, m set c using p select n from t;",
+        Statement::Query
+    );
+    let t = &query
+        .with
+        .expect("expected a with clause")
+        .common_table_expressions[0];
+    assert_eq!(
+        Some(SearchOrder::BreadthFirst),
+        t.search.as_ref().map(|search| search.order.clone())
+    );
+    assert_eq!(
+        Some(None),
+        t.cycle.as_ref().map(|cycle| cycle.mark_value.clone())
+    );
+}
+
+#[test]
+fn test_parse_invalid_common_table_expressions() {
+    assert_parse_fails(&[
+        "with select * from bike;",
+        "with t (select * from bike) select * from t;",
+        "with t as select * from bike select * from t;",
+        "with t as (select * from bike) select * from t",
+    ]);
+}
+
+// renders the tree of a set expression using the first table of each select,
+// e.g. (a UNION (b INTERSECT c))
+fn set_expression_shape(set_expression: &SetExpression) -> String {
+    match set_expression {
+        SetExpression::Select(select_statement) => {
+            match select_statement.from_expressions.first() {
+                Some(TableExpression::Table { name, .. }) => name.0.join("."),
+                other => panic!("expected a table but found {:?}", other),
+            }
+        }
+        SetExpression::Query(query) => format!("[{}]", set_expression_shape(&query.body)),
+        SetExpression::Values(rows) => format!("values{}", rows.len()),
+        SetExpression::SetOperation {
+            operator,
+            quantifier,
+            left,
+            right,
+        } => {
+            let quantifier = match quantifier {
+                Some(SetQuantifier::All) => " ALL",
+                Some(SetQuantifier::Distinct) => " DISTINCT",
+                _ => "",
+            };
+            format!(
+                "({} {:?}{} {})",
+                set_expression_shape(left),
+                operator,
+                quantifier,
+                set_expression_shape(right)
+            )
+        }
+    }
+}
+
+#[test]
+fn test_parse_set_operations() {
+    struct TestCase {
+        query: String,
+        expected_shape: String,
+    }
+
+    let test_cases = vec![
+        TestCase {
+            query: String::from("select id from a union select id from b;"),
+            expected_shape: String::from("(a Union b)"),
+        },
+        TestCase {
+            query: String::from(
+                "select id from a union all select id from b union distinct select id from c;",
+            ),
+            expected_shape: String::from("((a Union ALL b) Union DISTINCT c)"),
+        },
+        TestCase {
+            query: String::from(
+                "select id from a union select id from b intersect select id from c;",
+            ),
+            expected_shape: String::from("(a Union (b Intersect c))"),
+        },
+        TestCase {
+            query: String::from(
+                "select id from a intersect select id from b except select id from c;",
+            ),
+            expected_shape: String::from("((a Intersect b) Except c)"),
+        },
+        TestCase {
+            query: String::from("select id from a except all select id from b;"),
+            expected_shape: String::from("(a Except ALL b)"),
+        },
+        TestCase {
+            query: String::from(
+                "(select id from a union select id from b) intersect select id from c;",
+            ),
+            expected_shape: String::from("([(a Union b)] Intersect c)"),
+        },
+    ];
+
+    for test_case in test_cases {
+        println!("running test case: {}", test_case.query);
+        let query = parse_as!(&test_case.query, Statement::Query);
+        assert_eq!(test_case.expected_shape, set_expression_shape(&query.body));
+    }
+}
+
+#[test]
+fn test_parse_compound_query_order_by_and_limit() {
+    let query = parse_as!(
+        "(select id from a order by id limit 1) union all select id from b order by id desc limit 10;",
+        Statement::Query
+    );
+    assert_eq!(1, query.order_by.len());
+    assert!(query.limit.is_some());
+    match query.body {
+        SetExpression::SetOperation { left, right, .. } => {
+            match *left {
+                SetExpression::Query(inner) => {
+                    assert_eq!(1, inner.order_by.len());
+                    assert!(inner.limit.is_some());
+                }
+                other => panic!("expected a parenthesised query but found {:?}", other),
+            }
+            assert!(matches!(*right, SetExpression::Select(_)));
+        }
+        body => panic!("expected a set operation but found {:?}", body),
+    }
+
+    // subqueries accept full queries
+    let select_statement = parse_select(
+        "select * from (select id from a union select id from b) as ids where id = (select id from c except select id from d);",
+    );
+    match &select_statement.from_expressions[0] {
+        TableExpression::Select { query, .. } => {
+            assert_eq!("(a Union b)", set_expression_shape(&query.body))
+        }
+        other => panic!("expected a derived table but found {:?}", other),
+    }
+    match first_select_term(
+        "select (with t as (select id from bike) select max(id) from t) from store;",
+    ) {
+        Term::Subquery(subquery) => assert!(subquery.with.is_some()),
+        term => panic!("expected a subquery but found {:?}", term),
+    }
+}
+
+#[test]
+fn test_parse_invalid_set_operations() {
+    assert_parse_fails(&[
+        "select id from a union;",
+        "select id from a union all all select id from b;",
+        "select id from a union (select id from b;",
+        "select id from a order by id union select id from b;",
+    ]);
+}
+
+#[test]
+fn test_parse_select_without_from() {
+    struct TestCase {
+        query: String,
+        expected_select_expressions: usize,
+    }
+
+    let test_cases = vec![
+        TestCase {
+            query: String::from("select 1 + 1;"),
+            expected_select_expressions: 1,
+        },
+        TestCase {
+            query: String::from("select now() as started, 'bike' name;"),
+            expected_select_expressions: 2,
+        },
+        TestCase {
+            query: String::from("select 1 where true;"),
+            expected_select_expressions: 1,
+        },
+        TestCase {
+            query: String::from("select (select max(price) from bike);"),
+            expected_select_expressions: 1,
+        },
+    ];
+
+    for test_case in test_cases {
+        println!("running test case: {}", test_case.query);
+        let select_statement = parse_select(&test_case.query);
+        assert!(select_statement.from_expressions.is_empty());
+        assert_eq!(
+            test_case.expected_select_expressions,
+            select_statement.select_expressions.len()
+        );
+    }
+
+    let query = parse_as!("select 1 union all select 2 order by 1;", Statement::Query);
+    assert_eq!(1, query.order_by.len());
+}
+
+#[test]
+fn test_parse_values() {
+    let query = parse_as!("values (1, 'a'), (2 + 1, 'b');", Statement::Query);
+    assert_eq!(
+        SetExpression::Values(vec![
+            vec![
+                expression(int(1)),
+                expression(Term::Value(Value::String("a".to_string())))
+            ],
+            vec![
+                Term::Operand(Box::new(Operand::Addition(
+                    Box::new(Operand::Term(int(2))),
+                    Box::new(Operand::Term(int(1))),
+                ))),
+                expression(Term::Value(Value::String("b".to_string())))
+            ],
+        ]),
+        query.body
+    );
+
+    let query = parse_as!(
+        "values (1) union select id from bike order by 1 limit 5;",
+        Statement::Query
+    );
+    assert_eq!("(values1 Union bike)", set_expression_shape(&query.body));
+    assert!(query.limit.is_some());
+
+    let select_statement = parse_select(
+        "select * from (values (1, 'a'), (2, 'b')) as t (id, name) join bike on bike.id = t.id;",
+    );
+    match &select_statement.from_expressions[0] {
+        TableExpression::Join { left, .. } => match left.as_ref() {
+            TableExpression::Select { query, alias, .. } => {
+                assert_eq!("values2", set_expression_shape(&query.body));
+                assert_eq!(table_alias("t", &["id", "name"]), *alias);
+            }
+            other => panic!("expected a derived table but found {:?}", other),
+        },
+        other => panic!("expected a join but found {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_invalid_values() {
+    assert_parse_fails(&[
+        "values;",
+        "values ();",
+        "values (1), ;",
+        "values 1, 2;",
+        "values (1, 2), (3);",
+        "values (1), (2, 3);",
+        "values (default);",
+        "select * from (values (default, 1)) v;",
+        "select * from bike where id = (values (default));",
+        "insert into bike values (1, 2), (3);",
+        "select * from (values (1)) as t (id;",
+        "select 1,;",
+    ]);
+}
+
+#[test]
+fn test_parse_table_functions() {
+    let select_statement = parse_select(
+        "select * from generate_series(1, 10) as g(n), unnest(tags, ids) with ordinality t (tag, id, position);",
+    );
+    assert_eq!(
+        TableExpression::Function {
+            lateral: false,
+            function: Function::UserDefined {
+                name: object_name(&["generate_series"]),
+                terms: vec![expression(int(1)), expression(int(10))],
+            },
+            with_ordinality: false,
+            alias: table_alias("g", &["n"]),
+        },
+        select_statement.from_expressions[0]
+    );
+    assert_eq!(
+        TableExpression::Unnest {
+            lateral: false,
+            expressions: vec![expression(column("tags")), expression(column("ids"))],
+            with_ordinality: true,
+            alias: table_alias("t", &["tag", "id", "position"]),
+        },
+        select_statement.from_expressions[1]
+    );
+
+    let select_statement =
+        parse_select("select * from events e cross join lateral jsonb_array_elements(e.payload) with ordinality;");
+    match &select_statement.from_expressions[0] {
+        TableExpression::Join {
+            right, join_type, ..
+        } => {
+            assert_eq!(JoinType::Cross, *join_type);
+            assert!(matches!(
+                right.as_ref(),
+                TableExpression::Function {
+                    lateral: true,
+                    with_ordinality: true,
+                    alias: None,
+                    ..
+                }
+            ));
+        }
+        other => panic!("expected a join but found {:?}", other),
+    }
+
+    // lateral is still usable as a table name
+    assert_eq!(
+        table("lateral"),
+        parse_select("select * from lateral;").from_expressions[0]
+    );
+}
+
+#[test]
+fn test_parse_lateral_and_apply() {
+    struct TestCase {
+        query: String,
+        expected_join_type: JoinType,
+        expected_lateral: bool,
+    }
+
+    let test_cases = vec![
+        TestCase {
+            query: String::from(
+                "select * from bike b left join lateral (select * from rental r where r.bike_id = b.id limit 1) as last_rental on true;",
+            ),
+            expected_join_type: JoinType::Left,
+            expected_lateral: true,
+        },
+        TestCase {
+            query: String::from(
+                "select * from bike b cross apply (select top 1 * from rental r where r.bike_id = b.id) as last_rental;",
+            ),
+            expected_join_type: JoinType::CrossApply,
+            expected_lateral: false,
+        },
+        TestCase {
+            query: String::from(
+                "select * from bike b OUTER APPLY (select * from rental r where r.bike_id = b.id) last_rental;",
+            ),
+            expected_join_type: JoinType::OuterApply,
+            expected_lateral: false,
+        },
+    ];
+
+    for test_case in test_cases {
+        println!("running test case: {}", test_case.query);
+        let select_statement = parse_select(&test_case.query);
+        match &select_statement.from_expressions[0] {
+            TableExpression::Join {
+                right,
+                join_type,
+                constraint,
+                ..
+            } => {
+                assert_eq!(test_case.expected_join_type, *join_type);
+                if test_case.expected_join_type != JoinType::Left {
+                    assert_eq!(JoinConstraint::None, *constraint);
+                }
+                match right.as_ref() {
+                    TableExpression::Select { lateral, alias, .. } => {
+                        assert_eq!(test_case.expected_lateral, *lateral);
+                        assert_eq!(table_alias("last_rental", &[]), *alias);
+                    }
+                    other => panic!("expected a derived table but found {:?}", other),
+                }
+            }
+            other => panic!("expected a join but found {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn test_parse_invalid_table_functions() {
+    assert_parse_fails(&[
+        "select * from unnest() as t;",
+        "select * from generate_series(1, 10 as g;",
+        "select * from generate_series(1 10) g;",
+        "select * from generate_series(1,) g;",
+        "select * from unnest(tags,) t;",
+        "select * from lateral (bike join rental on true);",
+        "select * from bike cross apply;",
+        "select * from bike outer apply rental on true;",
+        "select * from unnest(tags) with t;",
+    ]);
+}
+
+#[test]
+fn test_parse_insert_values() {
+    let insert = parse_as!(
+        "insert into shop.bike (id, name, price) values (1, 'fixie', default), (2, 'bmx', 250);",
+        Statement::Insert
+    );
+    assert_eq!(None, insert.with);
+    assert_eq!(object_name(&["shop", "bike"]), insert.table);
+    assert_eq!(None, insert.alias);
+    assert_eq!(
+        vec!["id".to_string(), "name".to_string(), "price".to_string()],
+        insert.columns
+    );
+    match insert.source {
+        InsertSource::Query(query) => assert_eq!(
+            SetExpression::Values(vec![
+                vec![
+                    expression(int(1)),
+                    expression(Term::Value(Value::String("fixie".to_string()))),
+                    Term::Default,
+                ],
+                vec![
+                    expression(int(2)),
+                    expression(Term::Value(Value::String("bmx".to_string()))),
+                    expression(int(250)),
+                ],
+            ]),
+            query.body
+        ),
+        source => panic!("expected values but found {:?}", source),
+    }
+}
+
+#[test]
+fn test_parse_insert_sources() {
+    struct TestCase {
+        query: String,
+        expected_columns: usize,
+        expected_with: bool,
+        expected_source: String,
+    }
+
+    let test_cases = vec![
+        TestCase {
+            query: String::from("insert into bike values (1, 'fixie');"),
+            expected_columns: 0,
+            expected_with: false,
+            expected_source: String::from("values1"),
+        },
+        TestCase {
+            query: String::from("INSERT INTO bike AS b (id) SELECT id FROM old_bike;"),
+            expected_columns: 1,
+            expected_with: false,
+            expected_source: String::from("old_bike"),
+        },
+        TestCase {
+            query: String::from("insert into bike (select id from a union select id from old_bike);"),
+            expected_columns: 0,
+            expected_with: false,
+            expected_source: String::from("[(a Union old_bike)]"),
+        },
+        TestCase {
+            query: String::from(
+                "with old as (select * from old_bike) insert into bike (id, name) select id, name from old;",
+            ),
+            expected_columns: 2,
+            expected_with: true,
+            expected_source: String::from("old"),
+        },
+        TestCase {
+            query: String::from("insert into bike values (1), (2) union select id from old_bike;"),
+            expected_columns: 0,
+            expected_with: false,
+            expected_source: String::from("(values2 Union old_bike)"),
+        },
+        TestCase {
+            query: String::from("insert into bike default values;"),
+            expected_columns: 0,
+            expected_with: false,
+            expected_source: String::from("default values"),
+        },
+    ];
+
+    for test_case in test_cases {
+        println!("running test case: {}", test_case.query);
+        let insert = parse_as!(&test_case.query, Statement::Insert);
+        assert_eq!(test_case.expected_columns, insert.columns.len());
+        assert_eq!(test_case.expected_with, insert.with.is_some());
+        let source = match insert.source {
+            InsertSource::Query(query) => set_expression_shape(&query.body),
+            InsertSource::DefaultValues => String::from("default values"),
+        };
+        assert_eq!(test_case.expected_source, source);
+    }
+}
+
+#[test]
+fn test_parse_invalid_insert() {
+    assert_parse_fails(&[
+        "insert bike values (1);",
+        "insert into values (1);",
+        "insert into bike (id values (1);",
+        "insert into bike default;",
+        "insert into bike (id) default values (1);",
+        "insert into bike (id, name) values (1);",
+        "insert into bike (id) values (1, 'fixie');",
+        "insert into bike (id) values (1), (2, 'bmx');",
+        "insert into bike (id) (values (1, 'fixie'));",
+        "insert into bike values (default) union select id from old_bike;",
+        "insert into bike values (default) order by 1;",
+        "insert into bike select * from (values (default)) v;",
+        "insert into bike values (1), (2)",
+        "insert into bike;",
+    ]);
+}
+
+#[test]
+fn test_parse_update() {
+    let update = parse_as!(
+        "update bike as b set price = price * 2, name = default where id = 1;",
+        Statement::Update
+    );
+    assert_eq!(object_name(&["bike"]), update.table);
+    assert_eq!(Some("b".to_string()), update.alias);
+    assert_eq!(
+        vec![
+            Assignment {
+                target: AssignmentTarget::Column(object_name(&["price"])),
+                value: Term::Operand(Box::new(Operand::Multiplication(
+                    Box::new(Operand::Term(column("price"))),
+                    Box::new(Operand::Term(int(2))),
+                ))),
+            },
+            Assignment {
+                target: AssignmentTarget::Column(object_name(&["name"])),
+                value: Term::Default,
+            },
+        ],
+        update.assignments
+    );
+    assert!(update.from_expressions.is_empty());
+    assert!(update.where_expression.is_some());
+}
+
+#[test]
+fn test_parse_update_tuple_assignments_and_from() {
+    let update = parse_as!(
+        "update bike b set (price, name) = (1, default), (store_id) = (select id from store where name = 'main') from store s join rental r on r.store_id = s.id where b.store_id = s.id;",
+        Statement::Update
+    );
+    assert_eq!(Some("b".to_string()), update.alias);
+    assert_eq!(
+        Assignment {
+            target: AssignmentTarget::Tuple(vec![object_name(&["price"]), object_name(&["name"])]),
+            value: Term::Row(vec![expression(int(1)), Term::Default]),
+        },
+        update.assignments[0]
+    );
+    match &update.assignments[1] {
+        Assignment {
+            target: AssignmentTarget::Tuple(columns),
+            value: Term::Subquery(_),
+        } => assert_eq!(vec![object_name(&["store_id"])], *columns),
+        assignment => panic!("expected a subquery assignment but found {:?}", assignment),
+    }
+    assert_eq!(1, update.from_expressions.len());
+    assert!(matches!(
+        update.from_expressions[0],
+        TableExpression::Join { .. }
+    ));
+    assert!(update.where_expression.is_some());
+
+    let update = parse_as!(
+        "with cheap as (select id from bike where price < 10) update bike set price = 10 from cheap where bike.id = cheap.id;",
+        Statement::Update
+    );
+    assert!(update.with.is_some());
+    assert_eq!(None, update.alias);
+}
+
+#[test]
+fn test_parse_invalid_update() {
+    assert_parse_fails(&[
+        "update bike;",
+        "update set price = 1;",
+        "update bike set price;",
+        "update bike set price = 1,;",
+        "update bike set (price, name) = (1);",
+        "update bike set (price, name) = 1;",
+        "update bike set price = 1 where;",
+    ]);
+}
+
+#[test]
+fn test_parse_delete() {
+    let delete = parse_as!("delete from bike;", Statement::Delete);
+    assert!(delete.targets.is_empty());
+    assert_eq!(vec![table("bike")], delete.from_expressions);
+    assert!(delete.using.is_empty());
+    assert_eq!(None, delete.where_expression);
+
+    let delete = parse_as!(
+        "delete from shop.bike as b using rental r, store where b.id = r.bike_id and r.store_id = store.id;",
+        Statement::Delete
+    );
+    assert_eq!(
+        vec![TableExpression::Table {
+            name: object_name(&["shop", "bike"]),
+            alias: table_alias("b", &[]),
+        }],
+        delete.from_expressions
+    );
+    assert_eq!(
+        vec![
+            TableExpression::Table {
+                name: object_name(&["rental"]),
+                alias: table_alias("r", &[]),
+            },
+            table("store"),
+        ],
+        delete.using
+    );
+    assert!(delete.where_expression.is_some());
+
+    let delete = parse_as!(
+        "with old as (select id from rental where returned) delete from rental using old where rental.id = old.id;",
+        Statement::Delete
+    );
+    assert!(delete.with.is_some());
+}
+
+#[test]
+fn test_parse_multi_table_delete() {
+    let delete = parse_as!(
+        "DELETE b, r.* FROM bike b JOIN rental r ON r.bike_id = b.id WHERE b.retired = true;",
+        Statement::Delete
+    );
+    assert_eq!(
+        vec![object_name(&["b"]), object_name(&["r"])],
+        delete.targets
+    );
+    assert_eq!(1, delete.from_expressions.len());
+    assert!(matches!(
+        delete.from_expressions[0],
+        TableExpression::Join {
+            join_type: JoinType::Inner,
+            ..
+        }
+    ));
+    assert!(delete.where_expression.is_some());
+}
+
+#[test]
+fn test_parse_invalid_delete() {
+    assert_parse_fails(&[
+        "delete;",
+        "delete bike;",
+        "delete from;",
+        "delete from bike using;",
+        "delete from bike where;",
+        "delete b, from bike b;",
+        "delete from bike join store on bike.store_id = store.id;",
+        "delete from bike, store where bike.store_id = store.id;",
+        "delete from (select * from bike) b;",
+    ]);
+}
+
+#[test]
+fn test_parse_on_conflict() {
+    struct TestCase {
+        query: String,
+        expected_on_conflict: Option<OnConflict>,
+    }
+
+    let test_cases = vec![
+        TestCase {
+            query: String::from("insert into bike (id) values (1);"),
+            expected_on_conflict: None,
+        },
+        TestCase {
+            query: String::from("insert into bike (id) values (1) on conflict do nothing;"),
+            expected_on_conflict: Some(OnConflict::Conflict {
+                target: None,
+                action: ConflictAction::DoNothing,
+            }),
+        },
+        TestCase {
+            query: String::from(
+                "insert into bike (id) values (1) on conflict on constraint bike_pkey do nothing;",
+            ),
+            expected_on_conflict: Some(OnConflict::Conflict {
+                target: Some(ConflictTarget::Constraint(object_name(&["bike_pkey"]))),
+                action: ConflictAction::DoNothing,
+            }),
+        },
+        TestCase {
+            query: String::from(
+                "insert into bike as b (id, price) select id, price from import on conflict (id) do update set price = excluded.price where b.price > excluded.price;",
+            ),
+            expected_on_conflict: Some(OnConflict::Conflict {
+                target: Some(ConflictTarget::Columns(vec!["id".to_string()])),
+                action: ConflictAction::DoUpdate {
+                    assignments: vec![Assignment {
+                        target: AssignmentTarget::Column(object_name(&["price"])),
+                        value: expression(Term::Column(Column::Direct {
+                            name: object_name(&["excluded", "price"]),
+                        })),
+                    }],
+                    where_expression: Some(Term::Operand(Box::new(Operand::GreaterThan(
+                        Box::new(Operand::Term(Term::Column(Column::Direct {
+                            name: object_name(&["b", "price"]),
+                        }))),
+                        Box::new(Operand::Term(Term::Column(Column::Direct {
+                            name: object_name(&["excluded", "price"]),
+                        }))),
+                    )))),
+                },
+            }),
+        },
+        TestCase {
+            query: String::from(
+                "INSERT INTO bike (id, price) VALUES (1, 10) ON DUPLICATE KEY UPDATE price = VALUES(price);",
+            ),
+            expected_on_conflict: Some(OnConflict::DuplicateKeyUpdate(vec![Assignment {
+                target: AssignmentTarget::Column(object_name(&["price"])),
+                value: expression(Term::Function(Function::UserDefined {
+                    name: object_name(&["values"]),
+                    terms: vec![expression(column("price"))],
+                })),
+            }])),
+        },
+    ];
+
+    for test_case in test_cases {
+        println!("running test case: {}", test_case.query);
+        let insert = parse_as!(&test_case.query, Statement::Insert);
+        assert_eq!(test_case.expected_on_conflict, insert.on_conflict);
+    }
+}
+
+#[test]
+fn test_parse_returning() {
+    let insert = parse_as!(
+        "insert into bike (name) values ('fixie') on conflict do nothing returning id, name as bike_name;",
+        Statement::Insert
+    );
+    assert_eq!(
+        vec![
+            SelectExpression::Expression {
+                expression: expression(column("id")),
+                alias: None,
+            },
+            SelectExpression::Expression {
+                expression: expression(column("name")),
+                alias: Some("bike_name".to_string()),
+            },
+        ],
+        insert.returning
+    );
+    assert!(
+        parse_as!("insert into bike default values;", Statement::Insert)
+            .returning
+            .is_empty()
+    );
+
+    let update = parse_as!(
+        "update bike b set price = 1 where id = 2 returning b.*;",
+        Statement::Update
+    );
+    assert_eq!(
+        vec![SelectExpression::Family {
+            name: object_name(&["b"]),
+        }],
+        update.returning
+    );
+
+    let delete = parse_as!("delete from bike returning *;", Statement::Delete);
+    assert_eq!(vec![SelectExpression::Star], delete.returning);
+}
+
+#[test]
+fn test_parse_invalid_on_conflict_and_returning() {
+    assert_parse_fails(&[
+        "insert into bike values (1) on conflict;",
+        "insert into bike values (1) on conflict (id) do;",
+        "insert into bike values (1) on conflict do update price = 1;",
+        "insert into bike values (1) on conflict on bike_pkey do nothing;",
+        "insert into bike values (1) on duplicate key price = 1;",
+        "insert into bike values (1) returning;",
+        "update bike set price = 1 returning id,;",
+        "delete from bike returning from;",
+    ]);
+}
+
+#[test]
+fn test_parse_merge() {
+    let merge = parse_as!(
+        "merge into bike b using (select * from import) as i on b.id = i.id \
+         when matched and i.deleted then delete \
+         when matched then update set price = i.price \
+         when not matched then insert (id, price) values (i.id, default);",
+        Statement::Merge
+    );
+    assert_eq!(object_name(&["bike"]), merge.target);
+    assert_eq!(Some("b".to_string()), merge.alias);
+    match &merge.source {
+        TableExpression::Select { alias, .. } => assert_eq!(table_alias("i", &[]), *alias),
+        source => panic!("expected a derived table but found {:?}", source),
+    }
+    assert!(matches!(merge.on, Term::Operand(_)));
+    assert_eq!(
+        vec![
+            MergeClause {
+                kind: MergeClauseKind::Matched,
+                condition: Some(expression(Term::Column(Column::Direct {
+                    name: object_name(&["i", "deleted"]),
+                }))),
+                action: MergeAction::Delete,
+            },
+            MergeClause {
+                kind: MergeClauseKind::Matched,
+                condition: None,
+                action: MergeAction::Update(vec![Assignment {
+                    target: AssignmentTarget::Column(object_name(&["price"])),
+                    value: expression(Term::Column(Column::Direct {
+                        name: object_name(&["i", "price"]),
+                    })),
+                }]),
+            },
+            MergeClause {
+                kind: MergeClauseKind::NotMatched,
+                condition: None,
+                action: MergeAction::Insert {
+                    columns: vec!["id".to_string(), "price".to_string()],
+                    values: Some(vec![
+                        expression(Term::Column(Column::Direct {
+                            name: object_name(&["i", "id"]),
+                        })),
+                        Term::Default,
+                    ]),
+                },
+            },
+        ],
+        merge.clauses
+    );
+}
+
+#[test]
+fn test_parse_merge_clause_kinds() {
+    struct TestCase {
+        query: String,
+        expected_kinds: Vec<MergeClauseKind>,
+    }
+
+    let test_cases = vec![
+        TestCase {
+            query: String::from(
+                "MERGE INTO bike USING import ON bike.id = import.id WHEN NOT MATCHED BY TARGET THEN INSERT DEFAULT VALUES WHEN NOT MATCHED BY SOURCE THEN DELETE;",
+            ),
+            expected_kinds: vec![
+                MergeClauseKind::NotMatched,
+                MergeClauseKind::NotMatchedBySource,
+            ],
+        },
+        TestCase {
+            query: String::from(
+                "with i as (select * from import) merge into bike using i on bike.id = i.id when not matched by source and bike.active then update set active = false when matched then do nothing;",
+            ),
+            expected_kinds: vec![
+                MergeClauseKind::NotMatchedBySource,
+                MergeClauseKind::Matched,
+            ],
+        },
+    ];
+
+    for test_case in test_cases {
+        println!("running test case: {}", test_case.query);
+        let merge = parse_as!(&test_case.query, Statement::Merge);
+        let kinds: Vec<MergeClauseKind> = merge
+            .clauses
+            .into_iter()
+            .map(|clause| clause.kind)
+            .collect();
+        assert_eq!(test_case.expected_kinds, kinds);
+    }
+}
+
+#[test]
+fn test_parse_invalid_merge() {
+    assert_parse_fails(&[
+        "merge into bike using import on bike.id = import.id;",
+        "merge bike using import on bike.id = import.id when matched then delete;",
+        "merge into bike using import when matched then delete;",
+        "merge into bike using import on true when matched delete;",
+        "merge into bike using import on true when matched then insert values (1);",
+        "merge into bike using import on true when not matched then delete;",
+        "merge into bike using import on true when not matched by source then insert values (1);",
+        "merge into bike using import on true when not matched then insert values (1), (2);",
+        "merge into bike using import on true when not matched by bike then insert values (1);",
+    ]);
+}
+
+#[test]
+fn test_parse_merge_action_kind_mismatch() {
+    let queries = vec![
+        "merge into bike using import on true when not matched then update set price = 1;",
+        "merge into bike using import on true when matched then insert values (1);",
+        "merge into bike using import on true when not matched by source then insert values (1);",
+    ];
+
+    for query in queries {
+        println!("running test case: {}", query);
+        let mut parser = Parser::new(query.to_string(), false);
+        let err = parser
+            .parse()
+            .expect_err("expected the merge clause to be rejected");
+        assert!(matches!(
+            err.downcast_ref::<ParseError>(),
+            Some(ParseError::InvalidMergeClause(_))
+        ));
+    }
+}
+
+fn column_constraint(option: ColumnOption) -> ColumnConstraint {
+    ColumnConstraint { name: None, option }
+}
+
+#[test]
+fn test_parse_create_table_columns() {
+    let create_table = parse_as!(
+        "create table if not exists shop.bike (\
+            id bigint primary key, \
+            name varchar(100) not null unique collate \"C\", \
+            price decimal(10, 2) default 0 not null check (price >= 0), \
+            store_id integer constraint bike_store_fk references store (id) on delete cascade on update set null, \
+            notes text null\
+        );",
+        Statement::CreateTable
+    );
+    assert!(!create_table.temporary);
+    assert!(create_table.if_not_exists);
+    assert_eq!(object_name(&["shop", "bike"]), create_table.name);
+    assert!(create_table.constraints.is_empty());
+    assert_eq!(None, create_table.query);
+    assert_eq!(
+        vec![
+            ColumnDefinition {
+                name: "id".to_string(),
+                data_type: DataType::BigInt,
+                constraints: vec![column_constraint(ColumnOption::PrimaryKey)],
+            },
+            ColumnDefinition {
+                name: "name".to_string(),
+                data_type: DataType::Varchar(Some(100)),
+                constraints: vec![
+                    column_constraint(ColumnOption::NotNull),
+                    column_constraint(ColumnOption::Unique),
+                    column_constraint(ColumnOption::Collate(object_name(&["C"]))),
+                ],
+            },
+            ColumnDefinition {
+                name: "price".to_string(),
+                data_type: DataType::Decimal {
+                    precision: Some(10),
+                    scale: Some(2),
+                },
+                constraints: vec![
+                    column_constraint(ColumnOption::Default(expression(int(0)))),
+                    column_constraint(ColumnOption::NotNull),
+                    column_constraint(ColumnOption::Check(Term::Operand(Box::new(
+                        Operand::GreaterThanOrEqual(
+                            Box::new(Operand::Term(column("price"))),
+                            Box::new(Operand::Term(int(0))),
+                        )
+                    )))),
+                ],
+            },
+            ColumnDefinition {
+                name: "store_id".to_string(),
+                data_type: DataType::Integer,
+                constraints: vec![ColumnConstraint {
+                    name: Some("bike_store_fk".to_string()),
+                    option: ColumnOption::References(ForeignKeyReference {
+                        table: object_name(&["store"]),
+                        columns: vec!["id".to_string()],
+                        on_delete: Some(ReferentialAction::Cascade),
+                        on_update: Some(ReferentialAction::SetNull),
+                    }),
+                }],
+            },
+            ColumnDefinition {
+                name: "notes".to_string(),
+                data_type: DataType::Text,
+                constraints: vec![column_constraint(ColumnOption::Null)],
+            },
+        ],
+        create_table.columns
+    );
+}
+
+#[test]
+fn test_parse_create_table_constraints() {
+    let create_table = parse_as!(
+        "CREATE TEMPORARY TABLE rental (bike_id int, store_id int, started timestamp default now(), \
+         PRIMARY KEY (bike_id, started), \
+         CONSTRAINT rental_store_fk FOREIGN KEY (store_id) REFERENCES store ON DELETE NO ACTION, \
+         UNIQUE (bike_id), \
+         CONSTRAINT positive CHECK (bike_id > 0));",
+        Statement::CreateTable
+    );
+    assert!(create_table.temporary);
+    assert!(!create_table.if_not_exists);
+    assert_eq!(3, create_table.columns.len());
+    assert_eq!(
+        TableConstraint {
+            name: None,
+            kind: TableConstraintKind::PrimaryKey(vec![
+                "bike_id".to_string(),
+                "started".to_string()
+            ]),
+        },
+        create_table.constraints[0]
+    );
+    assert_eq!(
+        TableConstraint {
+            name: Some("rental_store_fk".to_string()),
+            kind: TableConstraintKind::ForeignKey {
+                columns: vec!["store_id".to_string()],
+                references: ForeignKeyReference {
+                    table: object_name(&["store"]),
+                    columns: vec![],
+                    on_delete: Some(ReferentialAction::NoAction),
+                    on_update: None,
+                },
+            },
+        },
+        create_table.constraints[1]
+    );
+    assert_eq!(
+        TableConstraintKind::Unique(vec!["bike_id".to_string()]),
+        create_table.constraints[2].kind
+    );
+    assert_eq!(
+        Some("positive".to_string()),
+        create_table.constraints[3].name
+    );
+    assert!(matches!(
+        create_table.constraints[3].kind,
+        TableConstraintKind::Check(_)
+    ));
+}
+
+#[test]
+fn test_parse_create_table_as() {
+    let create_table = parse_as!(
+        "create temp table cheap as select * from bike where price < 100;",
+        Statement::CreateTable
+    );
+    assert!(create_table.temporary);
+    assert!(create_table.columns.is_empty());
+    match create_table.query {
+        Some(query) => assert!(query_select(*query).where_expression.is_some()),
+        None => panic!("expected a query"),
+    }
+}
+
+#[test]
+fn test_parse_invalid_create_table() {
+    assert_parse_fails(&[
+        "create table;",
+        "create table bike;",
+        "create table bike ();",
+        "create table bike (id);",
+        "create table bike (id int,);",
+        "create table bike (id int not);",
+        "create table bike (id int primary);",
+        "create table bike (id int references);",
+        "create table bike (id int references store on delete nothing);",
+        "create table bike (id int, primary key id);",
+        "create table if exists bike (id int);",
+        "create table bike as;",
+    ]);
+}
+
+#[test]
+fn test_parse_alter_table_operations() {
+    struct TestCase {
+        query: String,
+        expected_operation: AlterTableOperation,
+    }
+
+    let test_cases = vec![
+        TestCase {
+            query: String::from("alter table bike add column if not exists color text not null;"),
+            expected_operation: AlterTableOperation::AddColumn {
+                if_not_exists: true,
+                column: ColumnDefinition {
+                    name: "color".to_string(),
+                    data_type: DataType::Text,
+                    constraints: vec![column_constraint(ColumnOption::NotNull)],
+                },
+            },
+        },
+        TestCase {
+            query: String::from("alter table bike add weight real;"),
+            expected_operation: AlterTableOperation::AddColumn {
+                if_not_exists: false,
+                column: ColumnDefinition {
+                    name: "weight".to_string(),
+                    data_type: DataType::Real,
+                    constraints: vec![],
+                },
+            },
+        },
+        TestCase {
+            query: String::from("alter table bike drop column if exists color cascade;"),
+            expected_operation: AlterTableOperation::DropColumn {
+                if_exists: true,
+                name: "color".to_string(),
+                drop_behavior: Some(DropBehavior::Cascade),
+            },
+        },
+        TestCase {
+            query: String::from("alter table bike drop weight;"),
+            expected_operation: AlterTableOperation::DropColumn {
+                if_exists: false,
+                name: "weight".to_string(),
+                drop_behavior: None,
+            },
+        },
+        TestCase {
+            query: String::from("alter table bike rename column name to model;"),
+            expected_operation: AlterTableOperation::RenameColumn {
+                old_name: "name".to_string(),
+                new_name: "model".to_string(),
+            },
+        },
+        TestCase {
+            query: String::from("alter table bike rename to bicycle;"),
+            expected_operation: AlterTableOperation::RenameTable(object_name(&["bicycle"])),
+        },
+        TestCase {
+            query: String::from(
+                "alter table bike alter column price type numeric(10, 2) using price::numeric;",
+            ),
+            expected_operation: AlterTableOperation::AlterColumn {
+                name: "price".to_string(),
+                operation: AlterColumnOperation::SetDataType {
+                    data_type: DataType::Decimal {
+                        precision: Some(10),
+                        scale: Some(2),
+                    },
+                    using: Some(expression(Term::Cast {
+                        kind: CastKind::DoubleColon,
+                        expression: Box::new(column("price")),
+                        data_type: DataType::Decimal {
+                            precision: None,
+                            scale: None,
+                        },
+                    })),
+                },
+            },
+        },
+        TestCase {
+            query: String::from("alter table bike alter price set data type bigint;"),
+            expected_operation: AlterTableOperation::AlterColumn {
+                name: "price".to_string(),
+                operation: AlterColumnOperation::SetDataType {
+                    data_type: DataType::BigInt,
+                    using: None,
+                },
+            },
+        },
+        TestCase {
+            query: String::from("alter table bike alter column price set default 10;"),
+            expected_operation: AlterTableOperation::AlterColumn {
+                name: "price".to_string(),
+                operation: AlterColumnOperation::SetDefault(expression(int(10))),
+            },
+        },
+        TestCase {
+            query: String::from("alter table bike alter column price drop default;"),
+            expected_operation: AlterTableOperation::AlterColumn {
+                name: "price".to_string(),
+                operation: AlterColumnOperation::DropDefault,
+            },
+        },
+        TestCase {
+            query: String::from("alter table bike alter column price set not null;"),
+            expected_operation: AlterTableOperation::AlterColumn {
+                name: "price".to_string(),
+                operation: AlterColumnOperation::SetNotNull,
+            },
+        },
+        TestCase {
+            query: String::from("alter table bike alter column price drop not null;"),
+            expected_operation: AlterTableOperation::AlterColumn {
+                name: "price".to_string(),
+                operation: AlterColumnOperation::DropNotNull,
+            },
+        },
+        TestCase {
+            query: String::from("alter table bike add constraint bike_name_key unique (name);"),
+            expected_operation: AlterTableOperation::AddConstraint(TableConstraint {
+                name: Some("bike_name_key".to_string()),
+                kind: TableConstraintKind::Unique(vec!["name".to_string()]),
+            }),
+        },
+        TestCase {
+            query: String::from(
+                "alter table bike drop constraint if exists bike_name_key restrict;",
+            ),
+            expected_operation: AlterTableOperation::DropConstraint {
+                if_exists: true,
+                name: "bike_name_key".to_string(),
+                drop_behavior: Some(DropBehavior::Restrict),
+            },
+        },
+    ];
+
+    for test_case in test_cases {
+        println!("running test case: {}", test_case.query);
+        let alter_table = parse_as!(&test_case.query, Statement::AlterTable);
+        assert!(!alter_table.if_exists);
+        assert_eq!(object_name(&["bike"]), alter_table.name);
+        assert_eq!(vec![test_case.expected_operation], alter_table.operations);
+    }
+}
+
+#[test]
+fn test_parse_alter_table_multiple_operations() {
+    let alter_table = parse_as!(
+        "ALTER TABLE IF EXISTS shop.bike ADD COLUMN color text, ALTER COLUMN price SET NOT NULL, ADD PRIMARY KEY (id);",
+        Statement::AlterTable
+    );
+    assert!(alter_table.if_exists);
+    assert_eq!(object_name(&["shop", "bike"]), alter_table.name);
+    assert_eq!(3, alter_table.operations.len());
+    assert!(matches!(
+        alter_table.operations[2],
+        AlterTableOperation::AddConstraint(TableConstraint {
+            name: None,
+            kind: TableConstraintKind::PrimaryKey(_),
+        })
+    ));
+}
+
+#[test]
+fn test_parse_invalid_alter_table() {
+    assert_parse_fails(&[
+        "alter table bike;",
+        "alter bike add color text;",
+        "alter table bike add;",
+        "alter table bike add column color;",
+        "alter table bike drop;",
+        "alter table bike rename color;",
+        "alter table bike rename to;",
+        "alter table bike alter column price;",
+        "alter table bike alter column price set;",
+        "alter table bike alter column price drop null;",
+        "alter table bike alter column price type;",
+        "alter table bike add color text,;",
+    ]);
+}
+
+fn drop_name(parts: &[&str]) -> DropName {
+    DropName {
+        name: object_name(parts),
+        parameter_types: None,
+    }
+}
+
+#[test]
+fn test_parse_drop() {
+    struct TestCase {
+        query: String,
+        expected_drop: DropStatement,
+    }
+
+    let test_cases = vec![
+        TestCase {
+            query: String::from("drop table bike;"),
+            expected_drop: DropStatement {
+                object_type: ObjectType::Table,
+                if_exists: false,
+                names: vec![drop_name(&["bike"])],
+                drop_behavior: None,
+            },
+        },
+        TestCase {
+            query: String::from("DROP TABLE IF EXISTS bike, shop.rental CASCADE;"),
+            expected_drop: DropStatement {
+                object_type: ObjectType::Table,
+                if_exists: true,
+                names: vec![drop_name(&["bike"]), drop_name(&["shop", "rental"])],
+                drop_behavior: Some(DropBehavior::Cascade),
+            },
+        },
+        TestCase {
+            query: String::from("drop view if exists cheap_bikes restrict;"),
+            expected_drop: DropStatement {
+                object_type: ObjectType::View,
+                if_exists: true,
+                names: vec![drop_name(&["cheap_bikes"])],
+                drop_behavior: Some(DropBehavior::Restrict),
+            },
+        },
+        TestCase {
+            query: String::from("drop materialized view bike_stats;"),
+            expected_drop: DropStatement {
+                object_type: ObjectType::MaterializedView,
+                if_exists: false,
+                names: vec![drop_name(&["bike_stats"])],
+                drop_behavior: None,
+            },
+        },
+        TestCase {
+            query: String::from("drop index bike_name_idx, bike_price_idx;"),
+            expected_drop: DropStatement {
+                object_type: ObjectType::Index,
+                if_exists: false,
+                names: vec![
+                    drop_name(&["bike_name_idx"]),
+                    drop_name(&["bike_price_idx"]),
+                ],
+                drop_behavior: None,
+            },
+        },
+        TestCase {
+            query: String::from("drop schema shop cascade;"),
+            expected_drop: DropStatement {
+                object_type: ObjectType::Schema,
+                if_exists: false,
+                names: vec![drop_name(&["shop"])],
+                drop_behavior: Some(DropBehavior::Cascade),
+            },
+        },
+        TestCase {
+            query: String::from("drop sequence bike_id_seq;"),
+            expected_drop: DropStatement {
+                object_type: ObjectType::Sequence,
+                if_exists: false,
+                names: vec![drop_name(&["bike_id_seq"])],
+                drop_behavior: None,
+            },
+        },
+        TestCase {
+            query: String::from("drop type if exists bike_color;"),
+            expected_drop: DropStatement {
+                object_type: ObjectType::Type,
+                if_exists: true,
+                names: vec![drop_name(&["bike_color"])],
+                drop_behavior: None,
+            },
+        },
+        TestCase {
+            query: String::from("drop function rent(int, text), shop.refresh(), cleanup;"),
+            expected_drop: DropStatement {
+                object_type: ObjectType::Function,
+                if_exists: false,
+                names: vec![
+                    DropName {
+                        name: object_name(&["rent"]),
+                        parameter_types: Some(vec![DataType::Integer, DataType::Text]),
+                    },
+                    DropName {
+                        name: object_name(&["shop", "refresh"]),
+                        parameter_types: Some(vec![]),
+                    },
+                    drop_name(&["cleanup"]),
+                ],
+                drop_behavior: None,
+            },
+        },
+    ];
+
+    for test_case in test_cases {
+        println!("running test case: {}", test_case.query);
+        let drop = parse_as!(&test_case.query, Statement::Drop);
+        assert_eq!(test_case.expected_drop, drop);
+    }
+}
+
+#[test]
+fn test_parse_truncate() {
+    struct TestCase {
+        query: String,
+        expected_truncate: TruncateStatement,
+    }
+
+    let test_cases = vec![
+        TestCase {
+            query: String::from("truncate bike;"),
+            expected_truncate: TruncateStatement {
+                names: vec![object_name(&["bike"])],
+                restart_identity: false,
+                drop_behavior: None,
+            },
+        },
+        TestCase {
+            query: String::from("TRUNCATE TABLE bike, shop.rental RESTART IDENTITY CASCADE;"),
+            expected_truncate: TruncateStatement {
+                names: vec![object_name(&["bike"]), object_name(&["shop", "rental"])],
+                restart_identity: true,
+                drop_behavior: Some(DropBehavior::Cascade),
+            },
+        },
+        TestCase {
+            query: String::from("truncate table bike continue identity restrict;"),
+            expected_truncate: TruncateStatement {
+                names: vec![object_name(&["bike"])],
+                restart_identity: false,
+                drop_behavior: Some(DropBehavior::Restrict),
+            },
+        },
+    ];
+
+    for test_case in test_cases {
+        println!("running test case: {}", test_case.query);
+        let truncate = parse_as!(&test_case.query, Statement::Truncate);
+        assert_eq!(test_case.expected_truncate, truncate);
+    }
+}
+
+#[test]
+fn test_parse_invalid_drop_and_truncate() {
+    assert_parse_fails(&[
+        "drop bike;",
+        "drop table;",
+        "drop table bike,;",
+        "drop table if bike;",
+        "drop materialized bike;",
+        "drop database shop;",
+        "drop table bike(int);",
+        "drop function rent(int,;",
+        "drop function rent(int,);",
+        "drop function rent(int text);",
+        "truncate;",
+        "truncate table;",
+        "truncate bike restart;",
+    ]);
+}
+
+#[test]
+fn test_parse_create_index() {
+    let create_index = parse_as!(
+        "create unique index concurrently if not exists bike_name_idx on shop.bike using btree (lower(name) desc nulls last, store_id) include (price) where retired = false;",
+        Statement::CreateIndex
+    );
+    assert!(create_index.unique);
+    assert!(create_index.concurrently);
+    assert!(create_index.if_not_exists);
+    assert_eq!("bike_name_idx", create_index.name);
+    assert_eq!(object_name(&["shop", "bike"]), create_index.table);
+    assert_eq!(Some("btree".to_string()), create_index.method);
+    assert_eq!(
+        vec![
+            OrderByExpr {
+                expression: expression(Term::Function(Function::UserDefined {
+                    name: object_name(&["lower"]),
+                    terms: vec![expression(column("name"))],
+                })),
+                direction: Some(OrderDirection::Desc),
+                nulls: Some(NullsOrder::Last),
+            },
+            OrderByExpr {
+                expression: expression(column("store_id")),
+                direction: None,
+                nulls: None,
+            },
+        ],
+        create_index.columns
+    );
+    assert_eq!(vec!["price".to_string()], create_index.include);
+    assert!(create_index.where_expression.is_some());
+
+    let create_index = parse_as!(
+        "CREATE INDEX bike_price_idx ON bike (price);",
+        Statement::CreateIndex
+    );
+    assert!(!create_index.unique);
+    assert!(!create_index.concurrently);
+    assert_eq!(None, create_index.method);
+    assert!(create_index.include.is_empty());
+    assert_eq!(None, create_index.where_expression);
+}
+
+#[test]
+fn test_parse_create_view() {
+    struct TestCase {
+        query: String,
+        expected_or_replace: bool,
+        expected_materialized: bool,
+        expected_columns: Vec<String>,
+        expected_with_data: Option<bool>,
+    }
+
+    let test_cases = vec![
+        TestCase {
+            query: String::from("create view cheap_bikes as select * from bike where price < 100;"),
+            expected_or_replace: false,
+            expected_materialized: false,
+            expected_columns: vec![],
+            expected_with_data: None,
+        },
+        TestCase {
+            query: String::from(
+                "create or replace view bike_names (id, name) as select id, name from bike union select id, name from old_bike;",
+            ),
+            expected_or_replace: true,
+            expected_materialized: false,
+            expected_columns: vec!["id".to_string(), "name".to_string()],
+            expected_with_data: None,
+        },
+        TestCase {
+            query: String::from(
+                "CREATE MATERIALIZED VIEW bike_stats AS SELECT store_id, count(*) FROM bike GROUP BY store_id WITH NO DATA;",
+            ),
+            expected_or_replace: false,
+            expected_materialized: true,
+            expected_columns: vec![],
+            expected_with_data: Some(false),
+        },
+        TestCase {
+            query: String::from(
+                "create materialized view bike_stats as with b as (select * from bike) select * from b with data;",
+            ),
+            expected_or_replace: false,
+            expected_materialized: true,
+            expected_columns: vec![],
+            expected_with_data: Some(true),
+        },
+    ];
+
+    for test_case in test_cases {
+        println!("running test case: {}", test_case.query);
+        let create_view = parse_as!(&test_case.query, Statement::CreateView);
+        assert_eq!(test_case.expected_or_replace, create_view.or_replace);
+        assert_eq!(test_case.expected_materialized, create_view.materialized);
+        assert_eq!(test_case.expected_columns, create_view.columns);
+        assert_eq!(test_case.expected_with_data, create_view.with_data);
+    }
+}
+
+#[test]
+fn test_parse_invalid_create_index_and_view() {
+    assert_parse_fails(&[
+        "create index on bike (name);",
+        "create index bike_idx bike (name);",
+        "create index bike_idx on bike;",
+        "create index bike_idx on bike ();",
+        "create index bike_idx on bike using (name);",
+        "create unique bike_idx on bike (name);",
+        "create view cheap_bikes;",
+        "create view cheap_bikes select * from bike;",
+        "create or view cheap_bikes as select * from bike;",
+        "create view cheap_bikes as select * from bike with data;",
+        "create materialized view stats as select * from bike with no;",
+    ]);
+}
+
+#[test]
+fn test_parse_transaction_statements() {
+    struct TestCase {
+        query: String,
+        expected_statement: Statement,
+    }
+
+    let test_cases = vec![
+        TestCase {
+            query: String::from("begin;"),
+            expected_statement: Statement::StartTransaction(vec![]),
+        },
+        TestCase {
+            query: String::from("BEGIN TRANSACTION ISOLATION LEVEL SERIALIZABLE, READ ONLY;"),
+            expected_statement: Statement::StartTransaction(vec![
+                TransactionMode::IsolationLevel(IsolationLevel::Serializable),
+                TransactionMode::AccessMode(TransactionAccessMode::ReadOnly),
+            ]),
+        },
+        TestCase {
+            query: String::from("start transaction read write isolation level repeatable read;"),
+            expected_statement: Statement::StartTransaction(vec![
+                TransactionMode::AccessMode(TransactionAccessMode::ReadWrite),
+                TransactionMode::IsolationLevel(IsolationLevel::RepeatableRead),
+            ]),
+        },
+        TestCase {
+            query: String::from("set transaction isolation level read committed;"),
+            expected_statement: Statement::SetTransaction(vec![TransactionMode::IsolationLevel(
+                IsolationLevel::ReadCommitted,
+            )]),
+        },
+        TestCase {
+            query: String::from("set transaction isolation level read uncommitted;"),
+            expected_statement: Statement::SetTransaction(vec![TransactionMode::IsolationLevel(
+                IsolationLevel::ReadUncommitted,
+            )]),
+        },
+        TestCase {
+            query: String::from("commit;"),
+            expected_statement: Statement::Commit,
+        },
+        TestCase {
+            query: String::from("COMMIT WORK;"),
+            expected_statement: Statement::Commit,
+        },
+        TestCase {
+            query: String::from("rollback;"),
+            expected_statement: Statement::Rollback(None),
+        },
+        TestCase {
+            query: String::from("rollback transaction to savepoint before_rental;"),
+            expected_statement: Statement::Rollback(Some("before_rental".to_string())),
+        },
+        TestCase {
+            query: String::from("rollback to before_rental;"),
+            expected_statement: Statement::Rollback(Some("before_rental".to_string())),
+        },
+        TestCase {
+            query: String::from("savepoint before_rental;"),
+            expected_statement: Statement::Savepoint("before_rental".to_string()),
+        },
+        TestCase {
+            query: String::from("release savepoint before_rental;"),
+            expected_statement: Statement::ReleaseSavepoint("before_rental".to_string()),
+        },
+        TestCase {
+            query: String::from("release before_rental;"),
+            expected_statement: Statement::ReleaseSavepoint("before_rental".to_string()),
+        },
+    ];
+
+    for test_case in test_cases {
+        println!("running test case: {}", test_case.query);
+        assert_eq!(
+            test_case.expected_statement,
+            parse_statement(&test_case.query)
+        );
+    }
+}
+
+#[test]
+fn test_parse_invalid_transaction_statements() {
+    assert_parse_fails(&[
+        "begin isolation;",
+        "begin isolation level read;",
+        "begin read;",
+        "begin read only,;",
+        "start read only;",
+        "set transaction;",
+        "set isolation level serializable;",
+        "rollback to;",
+        "savepoint;",
+        "release savepoint;",
+        "commit bike;",
+        "bike;",
+    ]);
+}